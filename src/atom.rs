@@ -0,0 +1,127 @@
+use anyhow::{anyhow, Result};
+use atom_syndication::{Content, Entry, Feed, FixedDateTime, Link, Person, Text};
+use chrono::{Datelike, Duration, Local, NaiveDate};
+use fluent_bundle::FluentArgs;
+use serde::Deserialize;
+
+use crate::calendar::{texts_for, UserTemplates};
+use crate::feed::Settings;
+use crate::i18n::{DEFAULT_LANGUAGE, I18N};
+use crate::models::FirstCommit;
+
+const DEFAULT_DAYS: i64 = 7;
+const MAX_DAYS: i64 = 365;
+
+/// Query string parameters for the trailing window of the Atom feed.
+#[derive(Debug, Default, Deserialize)]
+pub struct Trailing {
+    days: Option<String>,
+}
+
+impl Trailing {
+    /// How many days back (today included) birthdays are kept in the feed.
+    pub fn parse(&self) -> Result<i64> {
+        let days = match self.days.as_ref().filter(|v| !v.trim().is_empty()) {
+            None => return Ok(DEFAULT_DAYS),
+            Some(v) => v
+                .trim()
+                .parse::<i64>()
+                .map_err(|e| anyhow!("Invalid value for days, {}: {}", v, e))?,
+        };
+        if !(1..=MAX_DAYS).contains(&days) {
+            return Err(anyhow!(
+                "Invalid value for days, {}: must be between 1 and {}",
+                days,
+                MAX_DAYS
+            ));
+        }
+        Ok(days)
+    }
+}
+
+// entries are dated at midnight UTC of the birthday, so they never change once published
+fn timestamp(date: NaiveDate) -> FixedDateTime {
+    date.and_hms_opt(0, 0, 0)
+        .unwrap_or_default()
+        .and_utc()
+        .into()
+}
+
+fn entry_for(
+    commit: &FirstCommit,
+    date: NaiveDate,
+    settings: &Settings,
+    templates: &UserTemplates,
+) -> Result<Entry> {
+    let (title, description) = texts_for(commit, date, settings, templates)?;
+    let mut link = Link::default();
+    link.set_href(commit.url.as_str());
+    let mut content = Content::default();
+    content.set_content_type("text".to_string());
+    content.set_value(description);
+    let mut entry = Entry::default();
+    entry.set_id(format!(
+        "{}#birthday-{}",
+        commit.url,
+        commit.age(date.year())
+    ));
+    entry.set_title(Text::plain(title));
+    entry.set_updated(timestamp(date));
+    entry.set_published(timestamp(date));
+    entry.set_links(vec![link]);
+    entry.set_content(content);
+    Ok(entry)
+}
+
+/// Birthdays that happened in the last `days` days, most recent first.
+pub fn feed_from(
+    username: &str,
+    url: &str,
+    commits: &[FirstCommit],
+    settings: &Settings,
+    days: i64,
+) -> Result<Feed> {
+    let lang = settings.lang.as_deref().unwrap_or(DEFAULT_LANGUAGE);
+    let templates = UserTemplates::new(settings)?;
+    let today = Local::now().date_naive();
+    let since = today - Duration::days(days - 1);
+    let mut birthdays = vec![];
+    for commit in commits.iter().filter(|commit| settings.keeps(commit)) {
+        for year in since.year()..=today.year() {
+            if commit.age(year) < 1 || !settings.keeps_age(commit.age(year)) {
+                continue;
+            }
+            if let Some(date) = commit.anniversary_in(year, settings.leap_day)? {
+                if date >= since && date <= today {
+                    birthdays.push((date, commit));
+                }
+            }
+        }
+    }
+    birthdays.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.name.cmp(&b.1.name)));
+
+    let mut args = FluentArgs::new();
+    args.set("username", username);
+    let mut author = Person::default();
+    author.set_name(username);
+    author.set_uri(format!("https://github.com/{}", username));
+    let mut link = Link::default();
+    link.set_href(url);
+    link.set_rel("self");
+    let mut feed = Feed::default();
+    feed.set_id(url);
+    feed.set_title(I18N.message(lang, "calendar-name", Some(&args)));
+    feed.set_updated(timestamp(
+        birthdays.first().map_or(since, |(date, _)| *date),
+    ));
+    feed.set_authors(vec![author]);
+    feed.set_links(vec![link]);
+    feed.set_lang(lang.to_string());
+    feed.set_entries(
+        birthdays
+            .into_iter()
+            .map(|(date, commit)| entry_for(commit, date, settings, &templates))
+            .collect::<Result<Vec<_>>>()?,
+    );
+    Ok(feed)
+}
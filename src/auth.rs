@@ -1,9 +1,12 @@
 use crate::envvar;
 use anyhow::{anyhow, Result};
+use rand::{distr::Alphanumeric, Rng};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 
 const USER_AGENT: &str = "github.com/cuducos/repo-birthday";
+const RANDOM_TOKEN_LENGTH: usize = 32;
 
 #[derive(Deserialize)]
 struct UserInfo {
@@ -26,6 +29,20 @@ pub fn github_client_id() -> Result<String> {
     envvar::get("GITHUB_APP_CLIENT_ID")
 }
 
+/// A random alphanumeric string, long enough to be used as a secret.
+pub fn random_token() -> String {
+    rand::rng()
+        .sample_iter(&Alphanumeric)
+        .take(RANDOM_TOKEN_LENGTH)
+        .map(char::from)
+        .collect()
+}
+
+/// Secrets are only stored as this hash, so they cannot be recovered from the storage.
+pub fn hash(secret: &str) -> String {
+    format!("{:x}", Sha256::digest(secret.as_bytes()))
+}
+
 pub async fn token_for(client: &Client, code: &str) -> anyhow::Result<String> {
     let params = ExchangeToken {
         code: code.to_string(),
@@ -82,3 +99,32 @@ pub async fn username_for(client: &Client, token: &str) -> anyhow::Result<String
         }
     }
 }
+
+/// Scopes the user granted when logging in, as GitHub reports them for the token.
+pub async fn scopes_for(client: &Client, token: &str) -> anyhow::Result<Vec<String>> {
+    let res = client
+        .get("https://api.github.com/user")
+        .bearer_auth(token)
+        .header("Accept", "application/json")
+        .header("User-Agent", USER_AGENT)
+        .send()
+        .await?;
+    if !res.status().is_success() {
+        println!(
+            "Error getting scopes ({:?}): {:?}",
+            res.status(),
+            res.text().await?
+        );
+        return Err(anyhow!("Error getting scopes"));
+    }
+    let scopes = res
+        .headers()
+        .get("x-oauth-scopes")
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or("");
+    Ok(scopes
+        .split(',')
+        .map(|scope| scope.trim().to_string())
+        .filter(|scope| !scope.is_empty())
+        .collect())
+}
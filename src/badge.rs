@@ -0,0 +1,46 @@
+use chrono::Local;
+
+use crate::models::{FirstCommit, LeapDay};
+
+const HEIGHT: usize = 20;
+const PADDING: usize = 6;
+const LABEL_COLOR: &str = "#555";
+const VALUE_COLOR: &str = "#a333c8";
+
+/// Completed years since the first commit.
+pub fn age(commit: &FirstCommit) -> anyhow::Result<i32> {
+    commit.age_on(Local::now().date_naive(), LeapDay::default())
+}
+
+// rough width of Verdana at 11px, the font shields-style badges use
+fn text_width(text: &str) -> usize {
+    text.chars()
+        .map(|c| match c {
+            'i' | 'l' | 'j' | '.' | ',' | ':' | ' ' | '|' => 4,
+            'm' | 'w' | 'M' | 'W' => 10,
+            c if c.is_ascii_uppercase() => 8,
+            c if c.is_ascii() => 7,
+            _ => 11,
+        })
+        .sum()
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// A flat badge with a gray label on the left and a purple value on the right.
+pub fn svg(label: &str, value: &str) -> String {
+    let label_width = text_width(label) + 2 * PADDING;
+    let value_width = text_width(value) + 2 * PADDING;
+    let width = label_width + value_width;
+    let (label, value) = (escape(label), escape(value));
+    format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{HEIGHT}" role="img" aria-label="{label}: {value}"><title>{label}: {value}</title><linearGradient id="s" x2="0" y2="100%"><stop offset="0" stop-color="#bbb" stop-opacity=".1"/><stop offset="1" stop-opacity=".1"/></linearGradient><clipPath id="r"><rect width="{width}" height="{HEIGHT}" rx="3" fill="#fff"/></clipPath><g clip-path="url(#r)"><rect width="{label_width}" height="{HEIGHT}" fill="{LABEL_COLOR}"/><rect x="{label_width}" width="{value_width}" height="{HEIGHT}" fill="{VALUE_COLOR}"/><rect width="{width}" height="{HEIGHT}" fill="url(#s)"/></g><g fill="#fff" text-anchor="middle" font-family="Verdana,Geneva,DejaVu Sans,sans-serif" font-size="11"><text x="{label_x}" y="14">{label}</text><text x="{value_x}" y="14">{value}</text></g></svg>"##,
+        label_x = label_width / 2,
+        value_x = label_width + value_width / 2,
+    )
+}
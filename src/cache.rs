@@ -1,12 +1,41 @@
-use crate::envvar;
+use crate::{
+    envvar,
+    feed::{Preferences, Settings},
+    jobs::Job,
+    models::{Failure, FirstCommit, Firsts, OrgRegistration},
+    stargazers::StarHistory,
+};
 use anyhow::Result;
-use chrono::Duration;
+use chrono::{DateTime, Duration, Utc};
 use lazy_static::lazy_static;
 use magic_crypt::{new_magic_crypt, MagicCrypt256, MagicCryptTrait};
+use std::collections::BTreeMap;
 
 const TOKEN_SUFFIX: &str = "token";
 const CALENDAR_SUFFIX: &str = "calendar";
+const COMMITS_SUFFIX: &str = "commits";
+const FAILURES_SUFFIX: &str = "failures";
+const JOB_SUFFIX: &str = "job";
+const FEEDS_SUFFIX: &str = "feeds";
+const SECRET_SUFFIX: &str = "secret";
+const PREFERENCES_SUFFIX: &str = "preferences";
+const VARIANTS_SUFFIX: &str = "variants";
+// Keys starting with a username share the namespace with these prefixes, so they start with an
+// underscore, which GitHub logins cannot have.
+const SESSION_PREFIX: &str = "_session";
+const STARS_PREFIX: &str = "_stars";
+const REPO_PREFIX: &str = "_repo";
+const FIRSTS_PREFIX: &str = "_firsts";
+const ORG_PREFIX: &str = "_org";
+const PRIVATE_SUFFIX: &str = "private";
 const CALENDAR_TTL: Duration = Duration::days(1);
+const MAX_VARIANTS: usize = 20;
+const COMMITS_TTL: Duration = Duration::days(1);
+pub const JOB_TTL: Duration = Duration::days(1);
+pub const SESSION_TTL: Duration = Duration::days(30);
+const STARS_TTL: Duration = Duration::days(90);
+const FIRSTS_TTL: Duration = Duration::days(365);
+const INCOMPLETE_FIRSTS_TTL: Duration = Duration::days(7);
 
 lazy_static! {
     pub static ref CACHE: Cache = Cache::new().expect("Error building cache");
@@ -72,6 +101,62 @@ impl KVStorage {
         }
         Ok(resp.text().await?)
     }
+
+    /// Same as `get`, but distinguishes a missing key from a failed request.
+    async fn find(&self, key: &str) -> Result<Option<String>> {
+        let resp = reqwest::Client::new()
+            .get(self.url(key, None))
+            .header("User-Agent", "github.com/cuducos/repo-birthday")
+            .bearer_auth(&self.api_key)
+            .send()
+            .await?;
+        if resp.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !resp.status().is_success() {
+            return Err(anyhow::anyhow!(
+                "Request failed with status code {}: {}",
+                resp.status(),
+                resp.text().await?
+            ));
+        }
+        Ok(Some(resp.text().await?))
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        let resp = reqwest::Client::new()
+            .delete(self.url(key, None))
+            .header("User-Agent", "github.com/cuducos/repo-birthday")
+            .bearer_auth(&self.api_key)
+            .send()
+            .await?;
+        if !resp.status().is_success() && resp.status() != reqwest::StatusCode::NOT_FOUND {
+            return Err(anyhow::anyhow!(
+                "Request failed with status code {}: {}",
+                resp.status(),
+                resp.text().await?
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Which of the user's calendars a cache entry belongs to.
+pub enum Scope {
+    Default,
+    /// Calendars customized through query parameters, identified by `Settings::variant`.
+    Variant(String),
+    /// Calendars from feeds saved by the user, identified by the feed name.
+    Feed(String),
+}
+
+impl From<&Settings> for Scope {
+    fn from(settings: &Settings) -> Self {
+        match settings.variant() {
+            Some(variant) => Scope::Variant(variant),
+            None => Scope::Default,
+        }
+    }
 }
 
 pub struct Cache {
@@ -108,16 +193,288 @@ impl Cache {
         Ok(self.secret.decrypt_base64_to_string(value)?)
     }
 
-    pub async fn save_calendar(&self, user: &str, calendar: &str) -> Result<()> {
-        let key = self.to_key(&[user, CALENDAR_SUFFIX]);
+    fn calendar_key(&self, user: &str, scope: &Scope) -> String {
+        match scope {
+            Scope::Default => self.to_key(&[user, CALENDAR_SUFFIX]),
+            Scope::Variant(v) => self.to_key(&[user, CALENDAR_SUFFIX, v]),
+            Scope::Feed(name) => self.to_key(&[user, FEEDS_SUFFIX, name, CALENDAR_SUFFIX]),
+        }
+    }
+
+    /// Query strings are up to whoever requests the calendar, so only so many variants are cached
+    /// for each user at a time, keeping track of when each one expires.
+    async fn claim_variant(&self, user: &str, variant: &str) -> Result<bool> {
+        let key = self.to_key(&[user, VARIANTS_SUFFIX]);
+        let mut variants: BTreeMap<String, DateTime<Utc>> =
+            match self.storage.find(key.as_str()).await? {
+                Some(value) => serde_json::from_str(&value)?,
+                None => BTreeMap::new(),
+            };
+        let now = Utc::now();
+        variants.retain(|_, expires| *expires > now);
+        if !variants.contains_key(variant) && variants.len() >= MAX_VARIANTS {
+            return Ok(false);
+        }
+        variants.insert(variant.to_string(), now + CALENDAR_TTL);
+        let value = serde_json::to_string(&variants)?;
+        self.storage
+            .save(key.as_str(), value.as_str(), Some(CALENDAR_TTL))
+            .await?;
+        Ok(true)
+    }
+
+    pub async fn save_calendar(&self, user: &str, scope: &Scope, calendar: &str) -> Result<()> {
+        if let Scope::Variant(variant) = scope {
+            if !self.claim_variant(user, variant).await? {
+                return Ok(());
+            }
+        }
+        let key = self.calendar_key(user, scope);
         self.storage
             .save(key.as_str(), calendar, Some(CALENDAR_TTL))
             .await?;
         Ok(())
     }
 
-    pub async fn calendar(&self, user: &str) -> Result<String> {
-        let key = self.to_key(&[user, CALENDAR_SUFFIX]);
+    pub async fn calendar(&self, user: &str, scope: &Scope) -> Result<String> {
+        let key = self.calendar_key(user, scope);
         self.storage.get(key.as_str()).await
     }
+
+    pub async fn delete_calendar(&self, user: &str, scope: &Scope) -> Result<()> {
+        let key = self.calendar_key(user, scope);
+        self.storage.delete(key.as_str()).await
+    }
+
+    pub async fn save_commits(&self, user: &str, commits: &[FirstCommit]) -> Result<()> {
+        let key = self.to_key(&[user, COMMITS_SUFFIX]);
+        let value = serde_json::to_string(commits)?;
+        self.storage
+            .save(key.as_str(), value.as_str(), Some(COMMITS_TTL))
+            .await?;
+        Ok(())
+    }
+
+    pub async fn commits(&self, user: &str) -> Result<Vec<FirstCommit>> {
+        let key = self.to_key(&[user, COMMITS_SUFFIX]);
+        let value = self.storage.get(key.as_str()).await?;
+        Ok(serde_json::from_str(&value)?)
+    }
+
+    pub async fn save_failures(&self, user: &str, failures: &[Failure]) -> Result<()> {
+        let key = self.to_key(&[user, FAILURES_SUFFIX]);
+        let value = serde_json::to_string(failures)?;
+        self.storage
+            .save(key.as_str(), value.as_str(), Some(COMMITS_TTL))
+            .await?;
+        Ok(())
+    }
+
+    pub async fn failures(&self, user: &str) -> Result<Vec<Failure>> {
+        let key = self.to_key(&[user, FAILURES_SUFFIX]);
+        match self.storage.find(key.as_str()).await? {
+            Some(value) => Ok(serde_json::from_str(&value)?),
+            None => Ok(vec![]),
+        }
+    }
+
+    pub async fn save_job(&self, user: &str, job: &Job) -> Result<()> {
+        let key = self.to_key(&[user, JOB_SUFFIX]);
+        let value = serde_json::to_string(job)?;
+        self.storage
+            .save(key.as_str(), value.as_str(), Some(JOB_TTL))
+            .await?;
+        Ok(())
+    }
+
+    pub async fn job(&self, user: &str) -> Result<Option<Job>> {
+        let key = self.to_key(&[user, JOB_SUFFIX]);
+        match self.storage.find(key.as_str()).await? {
+            Some(value) => Ok(Some(serde_json::from_str(&value)?)),
+            None => Ok(None),
+        }
+    }
+
+    pub async fn save_feeds(&self, user: &str, feeds: &BTreeMap<String, Settings>) -> Result<()> {
+        let key = self.to_key(&[user, FEEDS_SUFFIX]);
+        let value = serde_json::to_string(feeds)?;
+        self.storage
+            .save(key.as_str(), value.as_str(), None)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn feeds(&self, user: &str) -> Result<BTreeMap<String, Settings>> {
+        let key = self.to_key(&[user, FEEDS_SUFFIX]);
+        match self.storage.find(key.as_str()).await? {
+            Some(value) => Ok(serde_json::from_str(&value)?),
+            None => Ok(BTreeMap::new()),
+        }
+    }
+
+    pub async fn save_secret(&self, user: &str, hash: &str) -> Result<()> {
+        let key = self.to_key(&[user, SECRET_SUFFIX]);
+        self.storage.save(key.as_str(), hash, None).await?;
+        Ok(())
+    }
+
+    /// The hash of the user's secret, if the user ever generated one.
+    pub async fn secret(&self, user: &str) -> Result<Option<String>> {
+        let key = self.to_key(&[user, SECRET_SUFFIX]);
+        self.storage.find(key.as_str()).await
+    }
+
+    pub async fn save_preferences(&self, user: &str, preferences: &Preferences) -> Result<()> {
+        let key = self.to_key(&[user, PREFERENCES_SUFFIX]);
+        let value = serde_json::to_string(preferences)?;
+        self.storage
+            .save(key.as_str(), value.as_str(), None)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn preferences(&self, user: &str) -> Result<Preferences> {
+        let key = self.to_key(&[user, PREFERENCES_SUFFIX]);
+        match self.storage.find(key.as_str()).await? {
+            Some(value) => Ok(serde_json::from_str(&value)?),
+            None => Ok(Preferences::default()),
+        }
+    }
+
+    pub async fn save_session(&self, session: &str, user: &str) -> Result<()> {
+        let key = self.to_key(&[SESSION_PREFIX, session]);
+        self.storage
+            .save(key.as_str(), user, Some(SESSION_TTL))
+            .await?;
+        Ok(())
+    }
+
+    pub async fn session(&self, session: &str) -> Result<String> {
+        let key = self.to_key(&[SESSION_PREFIX, session]);
+        self.storage.get(key.as_str()).await
+    }
+
+    /// GitHub names are case-insensitive, and so is this key.
+    fn repo_commit_key(&self, owner: &str, name: &str) -> String {
+        self.to_key(&[REPO_PREFIX, &owner.to_lowercase(), &name.to_lowercase()])
+    }
+
+    pub async fn save_repo_commit(&self, commit: &FirstCommit) -> Result<()> {
+        let key = self.repo_commit_key(&commit.owner, &commit.name);
+        let value = serde_json::to_string(commit)?;
+        self.storage
+            .save(key.as_str(), value.as_str(), Some(COMMITS_TTL))
+            .await?;
+        Ok(())
+    }
+
+    pub async fn repo_commit(&self, owner: &str, name: &str) -> Result<Option<FirstCommit>> {
+        let key = self.repo_commit_key(owner, name);
+        match self.storage.find(key.as_str()).await? {
+            Some(value) => Ok(Some(serde_json::from_str(&value)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Complete firsts are kept for a year, and the others checked again every week.
+    pub async fn save_firsts(&self, owner: &str, name: &str, firsts: &Firsts) -> Result<()> {
+        let key = self.to_key(&[FIRSTS_PREFIX, owner, name]);
+        let value = serde_json::to_string(firsts)?;
+        let ttl = if firsts.is_complete() {
+            FIRSTS_TTL
+        } else {
+            INCOMPLETE_FIRSTS_TTL
+        };
+        self.storage
+            .save(key.as_str(), value.as_str(), Some(ttl))
+            .await?;
+        Ok(())
+    }
+
+    pub async fn firsts(&self, owner: &str, name: &str) -> Result<Option<Firsts>> {
+        let key = self.to_key(&[FIRSTS_PREFIX, owner, name]);
+        match self.storage.find(key.as_str()).await? {
+            Some(value) => Ok(Some(serde_json::from_str(&value)?)),
+            None => Ok(None),
+        }
+    }
+
+    pub async fn save_star_history(
+        &self,
+        owner: &str,
+        name: &str,
+        history: &StarHistory,
+    ) -> Result<()> {
+        let key = self.to_key(&[STARS_PREFIX, owner, name]);
+        let value = serde_json::to_string(history)?;
+        self.storage
+            .save(key.as_str(), value.as_str(), Some(STARS_TTL))
+            .await?;
+        Ok(())
+    }
+
+    pub async fn star_history(&self, owner: &str, name: &str) -> Result<StarHistory> {
+        let key = self.to_key(&[STARS_PREFIX, owner, name]);
+        match self.storage.find(key.as_str()).await? {
+            Some(value) => Ok(serde_json::from_str(&value)?),
+            None => Ok(StarHistory::default()),
+        }
+    }
+
+    pub async fn save_org(&self, org: &str, registration: &OrgRegistration) -> Result<()> {
+        let key = self.to_key(&[ORG_PREFIX, org]);
+        let value = serde_json::to_string(registration)?;
+        self.storage
+            .save(key.as_str(), value.as_str(), None)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn org(&self, org: &str) -> Result<Option<OrgRegistration>> {
+        let key = self.to_key(&[ORG_PREFIX, org]);
+        match self.storage.find(key.as_str()).await? {
+            Some(value) => Ok(Some(serde_json::from_str(&value)?)),
+            None => Ok(None),
+        }
+    }
+
+    pub async fn save_org_secret(&self, org: &str, hash: &str) -> Result<()> {
+        let key = self.to_key(&[ORG_PREFIX, org, SECRET_SUFFIX]);
+        self.storage.save(key.as_str(), hash, None).await?;
+        Ok(())
+    }
+
+    pub async fn org_secret(&self, org: &str) -> Result<Option<String>> {
+        let key = self.to_key(&[ORG_PREFIX, org, SECRET_SUFFIX]);
+        self.storage.find(key.as_str()).await
+    }
+
+    fn org_commits_key(&self, org: &str, private: bool) -> String {
+        if private {
+            self.to_key(&[ORG_PREFIX, org, COMMITS_SUFFIX, PRIVATE_SUFFIX])
+        } else {
+            self.to_key(&[ORG_PREFIX, org, COMMITS_SUFFIX])
+        }
+    }
+
+    /// Organizations keep two lists, so public URLs never read the one with private repos.
+    pub async fn save_org_commits(
+        &self,
+        org: &str,
+        private: bool,
+        commits: &[FirstCommit],
+    ) -> Result<()> {
+        let key = self.org_commits_key(org, private);
+        let value = serde_json::to_string(commits)?;
+        self.storage
+            .save(key.as_str(), value.as_str(), Some(COMMITS_TTL))
+            .await?;
+        Ok(())
+    }
+
+    pub async fn org_commits(&self, org: &str, private: bool) -> Result<Vec<FirstCommit>> {
+        let key = self.org_commits_key(org, private);
+        let value = self.storage.get(key.as_str()).await?;
+        Ok(serde_json::from_str(&value)?)
+    }
 }
@@ -1,23 +1,331 @@
-use icalendar::{Calendar, Component, Event, EventLike};
+use anyhow::Result;
+use chrono::{Datelike, Duration, Local, NaiveDate};
+use fluent_bundle::FluentArgs;
+use icalendar::{Alarm, Calendar, Component, Event, EventLike, Property, Trigger};
+use liquid::Template;
 
-use crate::models::FirstCommit;
+use crate::feed::{Reminder, Settings};
+use crate::i18n::{DEFAULT_LANGUAGE, I18N};
+use crate::models::{Celebration, Failure, FirstCommit, Firsts, Occasion};
+use crate::templates::parse_user_template;
 
-pub fn calendar_from(username: &str, commits: &[FirstCommit]) -> Calendar {
+const MAX_TEMPLATE_MESSAGE_LENGTH: usize = 2000;
+const MAX_RENDERED_LENGTH: usize = 4000;
+const FAILURE_PROPERTY: &str = "X-REPO-BIRTHDAY-FAILURE";
+
+/// Templates from the feed settings, replacing the default summary and description.
+pub struct UserTemplates {
+    summary: Option<Template>,
+    description: Option<Template>,
+}
+
+impl UserTemplates {
+    pub fn new(settings: &Settings) -> Result<Self> {
+        Ok(Self {
+            summary: settings
+                .summary_template
+                .as_deref()
+                .map(parse_user_template)
+                .transpose()?,
+            description: settings
+                .description_template
+                .as_deref()
+                .map(parse_user_template)
+                .transpose()?,
+        })
+    }
+}
+
+/// Variables available to user templates.
+fn template_context(commit: &FirstCommit, date: NaiveDate, lang: &str) -> liquid::Object {
+    let age = commit.age(date.year());
+    let mut args = FluentArgs::new();
+    args.set("n", age);
+    let message: String = commit
+        .message
+        .chars()
+        .take(MAX_TEMPLATE_MESSAGE_LENGTH)
+        .collect();
+    liquid::object!({
+        "owner": commit.owner,
+        "name": commit.name,
+        "age": age,
+        "ordinal": I18N.message(lang, "ordinal", Some(&args)),
+        "date": date.format("%Y-%m-%d").to_string(),
+        "born": commit.date.format("%Y-%m-%d").to_string(),
+        "message": message,
+        "headline": commit.headline(),
+        "sha": commit.short_sha(),
+        "author": commit.author,
+        "stars": commit.stars,
+        "commits": commit.commits,
+        "language": commit.language,
+        "topics": commit.topics,
+        "description": commit.description,
+        "url": commit.url,
+        "commit_url": commit.commit_url,
+    })
+}
+
+fn render(template: &Template, context: &liquid::Object) -> Result<String> {
+    let rendered = template.render(context)?;
+    if rendered.chars().count() <= MAX_RENDERED_LENGTH {
+        return Ok(rendered);
+    }
+    let mut truncated: String = rendered.chars().take(MAX_RENDERED_LENGTH - 1).collect();
+    truncated.push('…');
+    Ok(truncated)
+}
+
+/// Checks a user template parses and renders against a sample repository.
+pub fn validate_template(source: &str) -> Result<()> {
+    let template = parse_user_template(source)?;
+    let sample = FirstCommit {
+        message: "Initial commit\n\nHello, world!".to_string(),
+        date: NaiveDate::from_ymd_opt(2015, 10, 21)
+            .and_then(|date| date.and_hms_opt(16, 29, 0))
+            .unwrap_or_default(),
+        name: "repo-birthday".to_string(),
+        owner: "cuducos".to_string(),
+        sha: "0123456789abcdef0123456789abcdef01234567".to_string(),
+        commit_url: "https://github.com/cuducos/repo-birthday/commit/0123456".to_string(),
+        author: Some("cuducos".to_string()),
+        url: "https://github.com/cuducos/repo-birthday".to_string(),
+        description: Some("Calendar of repository birthdays".to_string()),
+        language: Some("Rust".to_string()),
+        topics: vec!["calendar".to_string()],
+        stars: 42,
+        commits: 123,
+        first_release: None,
+        stable_release: None,
+        star_milestones: vec![],
+        firsts: Firsts::default(),
+    };
+    let date = Local::now().date_naive();
+    render(
+        &template,
+        &template_context(&sample, date, DEFAULT_LANGUAGE),
+    )?;
+    Ok(())
+}
+
+fn alarm_for(title: &str, reminder: &Reminder) -> Alarm {
+    let trigger = match reminder {
+        Reminder::Before(minutes) => Trigger::before_start(Duration::minutes(*minutes)),
+        Reminder::At(minutes) => Trigger::after_start(Duration::minutes(*minutes)),
+    };
+    Alarm::display(title, trigger)
+}
+
+fn description_for(commit: &FirstCommit, lang: &str) -> String {
+    let mut lines = vec![commit.headline().to_string()];
+    if !commit.body().is_empty() {
+        lines.push(String::new());
+        lines.push(commit.body().replace('\r', ""));
+    }
+    lines.push(String::new());
+    let mut args = FluentArgs::new();
+    args.set("sha", commit.short_sha());
+    args.set("date", commit.date.format("%Y-%m-%d").to_string());
+    lines.push(match &commit.author {
+        Some(author) => {
+            args.set("author", author.as_str());
+            I18N.message(lang, "first-commit-by", Some(&args))
+        }
+        None => I18N.message(lang, "first-commit", Some(&args)),
+    });
+    lines.push(I18N.message(lang, "born-on", Some(&args)));
+    lines.push(commit.commit_url.clone());
+    lines.push(String::new());
+    if let Some(description) = &commit.description {
+        lines.push(description.clone());
+    }
+    let mut details = vec![format!("★ {}", commit.stars)];
+    if let Some(language) = &commit.language {
+        details.insert(0, language.clone());
+    }
+    lines.push(details.join(" · "));
+    lines.push(commit.url.clone());
+    lines.join("\n")
+}
+
+/// Title and description of the celebration of a repo on a given date.
+pub fn texts_for(
+    commit: &FirstCommit,
+    date: NaiveDate,
+    settings: &Settings,
+    templates: &UserTemplates,
+) -> Result<(String, String)> {
+    let lang = settings.lang.as_deref().unwrap_or(DEFAULT_LANGUAGE);
+    let age = commit.age(date.year());
+    let context = template_context(commit, date, lang);
+    let title = match &templates.summary {
+        Some(template) => render(template, &context)?,
+        None => {
+            let mut args = FluentArgs::new();
+            args.set("repo", format!("{}/{}", commit.owner, commit.name));
+            args.set("age", age);
+            I18N.message(lang, "event-title", Some(&args))
+        }
+    };
+    let description = match &templates.description {
+        Some(template) => render(template, &context)?,
+        None => description_for(commit, lang),
+    };
+    Ok((title, description))
+}
+
+fn event_for(
+    commit: &FirstCommit,
+    date: NaiveDate,
+    settings: &Settings,
+    templates: &UserTemplates,
+) -> Result<Event> {
+    let age = commit.age(date.year());
+    let (title, description) = texts_for(commit, date, settings, templates)?;
+    let mut event = Event::new();
+    event
+        .all_day(date)
+        .summary(title.as_str())
+        .description(description.as_str())
+        .url(commit.url.as_str());
+    for reminder in settings.reminders_for(age) {
+        event.alarm(alarm_for(title.as_str(), reminder));
+    }
+    Ok(event.done())
+}
+
+pub fn calendar_from(
+    username: &str,
+    commits: &[FirstCommit],
+    failures: &[Failure],
+    settings: &Settings,
+) -> Result<Calendar> {
+    let lang = settings.lang.as_deref().unwrap_or(DEFAULT_LANGUAGE);
+    let mut args = FluentArgs::new();
+    args.set("username", username);
     let mut calendar = Calendar::new();
-    let calendar =
-        calendar.name(format!("{}'s  GitHub repository anniversaries", username).as_str());
-    for commit in commits {
-        let age = commit.age();
-        let pluralized = if age == 1 { "year" } else { "years" };
-        let title = format!(
-            "🎂 {}/{} ({} {} old)",
-            commit.owner, commit.name, age, pluralized
+    let calendar = calendar.name(I18N.message(lang, "calendar-name", Some(&args)).as_str());
+    if settings.failures {
+        for failure in failures {
+            calendar.append_property(Property::new(FAILURE_PROPERTY, failure.to_string()));
+        }
+    }
+    let templates = UserTemplates::new(settings)?;
+    for commit in commits.iter().filter(|commit| settings.keeps(commit)) {
+        for date in dates_for(commit, settings)? {
+            calendar.push(event_for(commit, date, settings, &templates)?);
+        }
+        for celebration in celebrations_for(commit, settings) {
+            calendar.push(celebration_event(commit, &celebration, settings));
+        }
+    }
+    Ok(calendar.done())
+}
+
+/// First and last days events are created for: the coming year, or the years in the window.
+fn range(settings: &Settings) -> (NaiveDate, NaiveDate) {
+    let today = Local::now().date_naive();
+    let year = today.year();
+    let first = |year: i32| NaiveDate::from_ymd_opt(year, 1, 1).unwrap_or(today);
+    let last = |year: i32| NaiveDate::from_ymd_opt(year, 12, 31).unwrap_or(today);
+    match settings.window {
+        None => (today, today + Duration::days(365)),
+        Some(window) => (
+            first(year - i32::from(window.past)),
+            last(year + i32::from(window.future)),
+        ),
+    }
+}
+
+/// Non-annual celebrations of a repo the feed opted into.
+pub fn celebrations_for(commit: &FirstCommit, settings: &Settings) -> Vec<Celebration> {
+    let (from, to) = range(settings);
+    settings
+        .occasions
+        .iter()
+        .flat_map(|occasion| commit.celebrations_between(*occasion, from, to, settings.leap_day))
+        .collect()
+}
+
+fn celebration_event(
+    commit: &FirstCommit,
+    celebration: &Celebration,
+    settings: &Settings,
+) -> Event {
+    let lang = settings.lang.as_deref().unwrap_or(DEFAULT_LANGUAGE);
+    let mut args = FluentArgs::new();
+    args.set("repo", format!("{}/{}", commit.owner, commit.name));
+    args.set("count", celebration.count);
+    let release = match celebration.occasion {
+        Occasion::FirstRelease => commit.first_release.as_ref(),
+        Occasion::StableRelease => commit.stable_release.as_ref(),
+        _ => None,
+    };
+    if let Some(release) = release {
+        args.set("tag", release.tag.as_str());
+    }
+    if let Some(stars) = celebration.stars {
+        args.set("stars", stars);
+    }
+    let first = commit.first(celebration.occasion);
+    if let Some(first) = first {
+        args.set("title", first.title.as_str());
+    }
+    let id = match celebration.occasion {
+        Occasion::DayCount => "event-day-count",
+        Occasion::PowerOfTwo => {
+            args.set("exponent", celebration.count.trailing_zeros());
+            "event-power-of-two"
+        }
+        Occasion::HalfBirthday => "event-half-birthday",
+        Occasion::Monthsary => "event-monthsary",
+        Occasion::FirstRelease => "event-first-release",
+        Occasion::StableRelease => "event-stable-release",
+        Occasion::StarMilestone => "event-star-milestone",
+        Occasion::FirstIssue => "event-first-issue",
+        Occasion::FirstPullRequest => "event-first-pull-request",
+        Occasion::FirstContribution => "event-first-contribution",
+        Occasion::FirstFork => "event-first-fork",
+    };
+    let title = I18N.message(lang, id, Some(&args));
+    let mut event = Event::new();
+    event
+        .all_day(celebration.date)
+        .summary(title.as_str())
+        .description(description_for(commit, lang).as_str())
+        .url(
+            release
+                .map(|release| release.url.as_str())
+                .or(first.map(|first| first.url.as_str()))
+                .unwrap_or(commit.url.as_str()),
         );
-        let event = Event::new()
-            .all_day(commit.next_anniversary())
-            .summary(title.as_str())
-            .done();
-        calendar.push(event);
+    // celebrations are not birthdays, so `reminders_for` and its milestone reminders do not apply
+    for reminder in &settings.reminders {
+        event.alarm(alarm_for(title.as_str(), reminder));
+    }
+    event.done()
+}
+
+/// Dates of the events of a repo, in the window of the feed and with the ages it keeps.
+pub fn dates_for(commit: &FirstCommit, settings: &Settings) -> Result<Vec<NaiveDate>> {
+    let mut dates = vec![];
+    match settings.window {
+        None => dates.push(commit.next_anniversary(settings.leap_day)?),
+        Some(window) => {
+            let this_year = Local::now().year();
+            let first = this_year - i32::from(window.past);
+            let last = this_year + i32::from(window.future);
+            for year in first..=last {
+                if commit.age(year) < 1 {
+                    continue;
+                }
+                if let Some(date) = commit.anniversary_in(year, settings.leap_day)? {
+                    dates.push(date);
+                }
+            }
+        }
     }
-    calendar.done()
+    dates.retain(|date| settings.keeps_age(commit.age(date.year())));
+    Ok(dates)
 }
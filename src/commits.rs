@@ -2,11 +2,13 @@ use anyhow::{anyhow, Result};
 use chrono::NaiveDateTime;
 use serde::Deserialize;
 
-use crate::date_time_serializer;
+use crate::date_time_serializer::{self, parse};
 use crate::graphql::GitHubGraphQL;
-use crate::models::FirstCommit;
+use crate::models::{FirstCommit, Firsts, Release};
 use crate::repositories::Repository as Repo;
 
+const MAX_RELEASE_PAGES: usize = 5;
+
 #[derive(Debug, Deserialize)]
 pub struct PageInfo {
     #[serde(rename = "endCursor")]
@@ -22,21 +24,57 @@ impl PageInfo {
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize)]
+pub struct User {
+    pub login: String,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Author {
+    pub name: Option<String>,
+    pub user: Option<User>,
+}
+
+impl Author {
+    fn display_name(&self) -> Option<String> {
+        self.user
+            .as_ref()
+            .map(|user| user.login.clone())
+            .or_else(|| self.name.clone())
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
 pub struct Commit {
+    pub oid: String,
+    pub url: String,
     pub message: String,
+    pub author: Option<Author>,
 
     #[serde(with = "date_time_serializer", rename = "committedDate")]
     pub committed_date: NaiveDateTime,
 }
 
 impl Commit {
-    fn as_first_commit(&self, repo: &Repo) -> FirstCommit {
+    fn as_first_commit(&self, repo: &Repo, total: i32) -> FirstCommit {
         FirstCommit {
             message: self.message.clone(),
             date: self.committed_date,
             name: repo.name.clone(),
             owner: repo.owner.login.clone(),
+            sha: self.oid.clone(),
+            commit_url: self.url.clone(),
+            author: self.author.as_ref().and_then(|a| a.display_name()),
+            url: repo.url.clone(),
+            description: repo.description.clone(),
+            language: repo.primary_language.as_ref().map(|l| l.name.clone()),
+            topics: repo.topics(),
+            stars: repo.stargazer_count,
+            commits: u32::try_from(total).unwrap_or_default(),
+            first_release: None,
+            stable_release: None,
+            star_milestones: vec![],
+            firsts: Firsts::default(),
         }
     }
 }
@@ -59,6 +97,54 @@ async fn last_commit_from_cursor(
         .and_then(|branch| branch.last_commit()))
 }
 
+async fn releases_page(
+    client: &GitHubGraphQL,
+    owner: &str,
+    name: &str,
+    cursor: &str,
+) -> Result<ReleasesRepository> {
+    let resp = client.releases(name, owner, cursor).await?;
+    let contents: ReleasesResponse = serde_json::from_str(&resp).map_err(|e| {
+        anyhow::anyhow!("Error parsing releases of {owner}/{name}: {}\n{}", e, resp)
+    })?;
+    Ok(contents.data.repository)
+}
+
+/// The first release and the oldest 1.0 of a repo. Drafts come first for owners, so releases are
+/// paged through until a published one shows up; if that fails, the repo has no first release.
+pub async fn releases(
+    client: &GitHubGraphQL,
+    commit: &FirstCommit,
+) -> Result<(Option<Release>, Option<Release>)> {
+    let (owner, name) = (commit.owner.as_str(), commit.name.as_str());
+    let mut repository = releases_page(client, owner, name, "").await?;
+    for _ in 0..MAX_RELEASE_PAGES {
+        let releases = &repository.releases;
+        let published = releases
+            .nodes
+            .iter()
+            .any(|release| !release.is_draft && release.published_at.is_some());
+        if published || !releases.page_info.has_next_page {
+            break;
+        }
+        let cursor = releases.page_info.end_cursor.clone().unwrap_or_default();
+        match releases_page(client, owner, name, &cursor).await {
+            Ok(page) => {
+                repository.releases.nodes.extend(page.releases.nodes);
+                repository.releases.page_info = page.releases.page_info;
+            }
+            Err(e) => {
+                eprintln!("Error paging through the releases of {owner}/{name}: {e}");
+                break;
+            }
+        }
+    }
+    Ok((
+        repository.first_release(),
+        repository.stable_release(&commit.url),
+    ))
+}
+
 pub async fn last_commit(client: &GitHubGraphQL, repo: &Repo) -> Result<Option<FirstCommit>> {
     let resp = client
         .cursor_or_last_commit(repo.name.as_str(), repo.owner.login.as_str())
@@ -71,21 +157,22 @@ pub async fn last_commit(client: &GitHubGraphQL, repo: &Repo) -> Result<Option<F
         )
     })?;
 
-    match contents.data.repository.default_branch_ref {
+    match &contents.data.repository.default_branch_ref {
         None => Ok(None),
         Some(branch) => {
-            if branch.target.history.total_count == 0 {
+            let total = branch.target.history.total_count;
+            if total == 0 {
                 return Ok(None);
             }
-            if branch.target.history.total_count == 1 {
+            if total == 1 {
                 if let Some(commit) = branch.target.history.nodes.first() {
-                    return Ok(Some(commit.as_first_commit(repo)));
+                    return Ok(Some(commit.as_first_commit(repo, total)));
                 }
             }
 
             let cursor = branch.last_commit_cursor()?;
             match last_commit_from_cursor(client, repo, cursor).await? {
-                Some(commit) => Ok(Some(commit.as_first_commit(repo))),
+                Some(commit) => Ok(Some(commit.as_first_commit(repo, total))),
                 None => Ok(None),
             }
         }
@@ -128,19 +215,139 @@ impl Branch {
     }
 
     pub fn last_commit(&self) -> Option<Commit> {
-        self.target.history.nodes.last().map(|commit| Commit {
-            message: commit.message.clone(),
-            committed_date: commit.committed_date,
-        })
+        self.target.history.nodes.last().cloned()
     }
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReleaseNode {
+    pub tag_name: String,
+    pub url: String,
+    pub is_draft: bool,
+    pub published_at: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ReleasesPageInfo {
+    #[serde(rename = "hasNextPage")]
+    pub has_next_page: bool,
+
+    #[serde(rename = "endCursor")]
+    pub end_cursor: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct Releases {
+    pub nodes: Vec<ReleaseNode>,
+
+    #[serde(default, rename = "pageInfo")]
+    pub page_info: ReleasesPageInfo,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Tagger {
+    pub date: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TagTarget {
+    pub committed_date: Option<String>,
+    pub tagger: Option<Tagger>,
+    pub target: Option<Box<TagTarget>>,
+}
+
+impl TagTarget {
+    /// Annotated tags are dated when they were tagged, lightweight ones by the commit they point to.
+    fn date(&self) -> Option<NaiveDateTime> {
+        self.tagger
+            .as_ref()
+            .and_then(|tagger| tagger.date.as_deref())
+            .or(self.committed_date.as_deref())
+            .and_then(|date| parse(date).ok())
+            .or_else(|| self.target.as_ref().and_then(|target| target.date()))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Tag {
+    pub name: String,
+    pub target: Option<TagTarget>,
+}
+
+fn is_stable(tag: &str) -> bool {
+    matches!(tag.strip_prefix('v').unwrap_or(tag), "1.0" | "1.0.0")
+}
+
 #[derive(Debug, Deserialize)]
 pub struct Repository {
     #[serde(rename = "defaultBranchRef")]
     pub default_branch_ref: Option<Branch>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct ReleasesRepository {
+    pub releases: Releases,
+
+    // the candidate names are looked up one by one, since searching tags matches substrings
+    #[serde(default, rename = "tag10")]
+    pub tag_1_0: Option<Tag>,
+
+    #[serde(default, rename = "tag100")]
+    pub tag_1_0_0: Option<Tag>,
+
+    #[serde(default, rename = "tagV10")]
+    pub tag_v1_0: Option<Tag>,
+
+    #[serde(default, rename = "tagV100")]
+    pub tag_v1_0_0: Option<Tag>,
+}
+
+impl ReleasesRepository {
+    fn published(&self) -> impl Iterator<Item = Release> + '_ {
+        self.releases
+            .nodes
+            .iter()
+            .filter(|release| !release.is_draft)
+            .filter_map(|release| {
+                let date = parse(release.published_at.as_deref()?).ok()?;
+                Some(Release {
+                    tag: release.tag_name.clone(),
+                    date,
+                    url: release.url.clone(),
+                })
+            })
+    }
+
+    fn first_release(&self) -> Option<Release> {
+        self.published().min_by_key(|release| release.date)
+    }
+
+    /// The oldest 1.0 (or 1.0.0, with or without a `v`) tag, released or not.
+    fn stable_release(&self, url: &str) -> Option<Release> {
+        let tags = [
+            &self.tag_1_0,
+            &self.tag_1_0_0,
+            &self.tag_v1_0,
+            &self.tag_v1_0_0,
+        ]
+        .into_iter()
+        .flatten()
+        .filter_map(|tag| {
+            Some(Release {
+                tag: tag.name.clone(),
+                date: tag.target.as_ref()?.date()?,
+                url: format!("{}/releases/tag/{}", url, tag.name),
+            })
+        });
+        self.published()
+            .filter(|release| is_stable(&release.tag))
+            .chain(tags)
+            .min_by_key(|release| release.date)
+    }
+}
+
 #[derive(Debug, Deserialize)]
 pub struct Data {
     pub repository: Repository,
@@ -150,3 +357,13 @@ pub struct Data {
 pub struct Response {
     pub data: Data,
 }
+
+#[derive(Debug, Deserialize)]
+pub struct ReleasesData {
+    pub repository: ReleasesRepository,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReleasesResponse {
+    pub data: ReleasesData,
+}
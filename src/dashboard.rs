@@ -0,0 +1,162 @@
+use anyhow::Result;
+use chrono::Local;
+use fluent_bundle::FluentArgs;
+use serde::Deserialize;
+
+use crate::i18n::I18N;
+use crate::models::{FirstCommit, LeapDay};
+
+const MAX_QUERY_LENGTH: usize = 100;
+
+/// Query string parameters of the user's page, sorting and filtering the table of repositories
+/// on the server, so it works without JavaScript.
+#[derive(Debug, Default, Deserialize)]
+pub struct TableParams {
+    sort: Option<String>,
+    order: Option<String>,
+    q: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Column {
+    Repository,
+    Born,
+    Age,
+    Countdown,
+}
+
+impl Column {
+    fn from_param(value: Option<&str>) -> Self {
+        match value {
+            Some("repository") => Self::Repository,
+            Some("born") => Self::Born,
+            Some("age") => Self::Age,
+            _ => Self::Countdown,
+        }
+    }
+
+    fn param(&self) -> &'static str {
+        match self {
+            Self::Repository => "repository",
+            Self::Born => "born",
+            Self::Age => "age",
+            Self::Countdown => "countdown",
+        }
+    }
+}
+
+struct Row<'a> {
+    commit: &'a FirstCommit,
+    age: i32,
+    days: i64,
+    next_anniversary: String,
+}
+
+/// Table of repositories and the links sorting it, ready for the HTML template.
+pub fn table(
+    commits: &[FirstCommit],
+    params: &TableParams,
+    policy: LeapDay,
+    lang: &str,
+) -> Result<liquid::Object> {
+    let today = Local::now().date_naive();
+    let sort = Column::from_param(params.sort.as_deref());
+    let descending = params.order.as_deref() == Some("desc");
+    let query: String = params
+        .q
+        .as_deref()
+        .unwrap_or("")
+        .trim()
+        .chars()
+        .take(MAX_QUERY_LENGTH)
+        .collect();
+    let needle = query.to_lowercase();
+    let mut rows = vec![];
+    for commit in commits {
+        let matches = needle.is_empty()
+            || format!("{}/{}", commit.owner, commit.name)
+                .to_lowercase()
+                .contains(&needle)
+            || commit.headline().to_lowercase().contains(&needle);
+        if !matches {
+            continue;
+        }
+        let next_anniversary = commit.next_anniversary(policy)?;
+        rows.push(Row {
+            commit,
+            age: commit.age_on(today, policy)?,
+            days: commit.days_to_next_anniversary(policy)?,
+            next_anniversary: next_anniversary.format("%Y-%m-%d").to_string(),
+        });
+    }
+    rows.sort_by(|a, b| {
+        let name = |row: &Row| format!("{}/{}", row.commit.owner, row.commit.name).to_lowercase();
+        let ordering = match sort {
+            Column::Repository => name(a).cmp(&name(b)),
+            Column::Born => a.commit.date.cmp(&b.commit.date),
+            Column::Age => a.age.cmp(&b.age),
+            Column::Countdown => a.days.cmp(&b.days),
+        };
+        ordering.then_with(|| name(a).cmp(&name(b)))
+    });
+    if descending {
+        rows.reverse();
+    }
+
+    let rows: Vec<liquid::Object> = rows
+        .iter()
+        .map(|row| {
+            let mut args = FluentArgs::new();
+            args.set("days", row.days);
+            liquid::object!({
+                "owner": row.commit.owner,
+                "name": row.commit.name,
+                "url": row.commit.url,
+                "born": row.commit.date.format("%Y-%m-%d").to_string(),
+                "age": row.age,
+                "next_anniversary": row.next_anniversary,
+                "countdown": I18N.message(lang, "countdown", Some(&args)),
+                "headline": row.commit.headline(),
+                "commit_url": row.commit.commit_url,
+            })
+        })
+        .collect();
+    // each column header links to sorting by it, reversing the order if it is already sorted
+    let mut links = liquid::Object::new();
+    for column in [
+        Column::Repository,
+        Column::Born,
+        Column::Age,
+        Column::Countdown,
+    ] {
+        let order = if column == sort && !descending {
+            "desc"
+        } else {
+            "asc"
+        };
+        let mut link = format!("?sort={}&order={}", column.param(), order);
+        if !query.is_empty() {
+            link.push_str(&format!("&q={}", encode(&query)));
+        }
+        links.insert(column.param().into(), liquid::model::Value::scalar(link));
+    }
+    Ok(liquid::object!({
+        "rows": rows,
+        "links": links,
+        "query": query,
+        "sort": sort.param(),
+        "descending": descending,
+    }))
+}
+
+fn encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
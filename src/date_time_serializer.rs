@@ -1,12 +1,18 @@
-use chrono::NaiveDateTime;
+use chrono::{DateTime, NaiveDateTime};
 use serde::{self, Deserialize, Deserializer};
 
 const DATE_FORMAT: &str = "%Y-%m-%dT%H:%M:%SZ";
 
+/// Parses GitHub dates, in UTC (e.g. commits) or with an offset (e.g. taggers).
+pub fn parse(value: &str) -> Result<NaiveDateTime, chrono::ParseError> {
+    NaiveDateTime::parse_from_str(value, DATE_FORMAT)
+        .or_else(|_| DateTime::parse_from_rfc3339(value).map(|date| date.naive_utc()))
+}
+
 pub fn deserialize<'de, D>(deserializer: D) -> Result<NaiveDateTime, D::Error>
 where
     D: Deserializer<'de>,
 {
     let s = String::deserialize(deserializer)?;
-    NaiveDateTime::parse_from_str(&s, DATE_FORMAT).map_err(serde::de::Error::custom)
+    parse(&s).map_err(serde::de::Error::custom)
 }
@@ -0,0 +1,148 @@
+use anyhow::Result;
+use fluent_bundle::FluentArgs;
+use serde::Serialize;
+
+use crate::calendar::{celebrations_for, dates_for};
+use crate::feed::Settings;
+use crate::i18n::I18N;
+use crate::models::{Failure, FirstCommit};
+use crate::repositories::Repository;
+
+/// Why a repo is not in the calendar.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Reason {
+    Fork,
+    Private,
+    Empty,
+    NoDefaultBranch,
+    Failed,
+    Pending,
+    NoCommits,
+    Filtered,
+    NoEvents,
+}
+
+impl Reason {
+    fn message_id(&self) -> &'static str {
+        match self {
+            Self::Fork => "explain-fork",
+            Self::Private => "explain-private",
+            Self::Empty => "explain-empty",
+            Self::NoDefaultBranch => "explain-no-default-branch",
+            Self::Failed => "explain-failed",
+            Self::Pending => "explain-pending",
+            Self::NoCommits => "explain-no-commits",
+            Self::Filtered => "explain-filtered",
+            Self::NoEvents => "explain-no-events",
+        }
+    }
+}
+
+/// The decision about one repository returned by GitHub.
+#[derive(Clone, Debug, Serialize)]
+pub struct Entry {
+    pub repo: String,
+    pub url: String,
+    pub included: bool,
+    pub reason: Option<Reason>,
+    pub detail: Option<String>,
+    pub branch: Option<String>,
+    pub date_source: Option<&'static str>,
+    pub born: Option<String>,
+    pub events: usize,
+}
+
+impl Entry {
+    fn new(repo: &Repository) -> Self {
+        Self {
+            repo: repo.to_string(),
+            url: repo.url.clone(),
+            included: false,
+            reason: None,
+            detail: None,
+            branch: repo.default_branch_ref.as_ref().map(|b| b.name.clone()),
+            date_source: None,
+            born: None,
+            events: 0,
+        }
+    }
+
+    fn excluded(mut self, reason: Reason, detail: Option<String>) -> Self {
+        self.reason = Some(reason);
+        self.detail = detail;
+        self
+    }
+}
+
+/// Follows the same steps as the calendar: listing repos, finding their first commits (`None`
+/// while they are still being looked for), and applying the settings of the feed.
+pub fn explain(
+    repos: &[Repository],
+    commits: Option<&[FirstCommit]>,
+    failures: &[Failure],
+    settings: &Settings,
+) -> Result<Vec<Entry>> {
+    let mut entries = Vec::with_capacity(repos.len());
+    for repo in repos {
+        let entry = Entry::new(repo);
+        let name = entry.repo.clone();
+        let entry = if repo.is_fork {
+            entry.excluded(Reason::Fork, None)
+        } else if repo.is_private {
+            entry.excluded(Reason::Private, None)
+        } else if repo.is_empty {
+            entry.excluded(Reason::Empty, None)
+        } else if repo.default_branch_ref.is_none() {
+            entry.excluded(Reason::NoDefaultBranch, None)
+        } else if let Some(failure) = failures.iter().find(|f| f.repo == name) {
+            entry.excluded(Reason::Failed, Some(failure.error.clone()))
+        } else if let Some(commits) = commits {
+            match commits
+                .iter()
+                .find(|c| c.owner == repo.owner.login && c.name == repo.name)
+            {
+                None => entry.excluded(Reason::NoCommits, None),
+                Some(commit) => decide(entry, commit, settings)?,
+            }
+        } else {
+            entry.excluded(Reason::Pending, None)
+        };
+        entries.push(entry);
+    }
+    entries.sort_by(|a, b| {
+        (a.included, &a.repo.to_lowercase()).cmp(&(b.included, &b.repo.to_lowercase()))
+    });
+    Ok(entries)
+}
+
+fn decide(mut entry: Entry, commit: &FirstCommit, settings: &Settings) -> Result<Entry> {
+    entry.date_source = Some(commit.date_source());
+    entry.born = Some(commit.date.format("%Y-%m-%d").to_string());
+    if let Some(option) = settings.filtered_by(commit) {
+        return Ok(entry.excluded(Reason::Filtered, Some(option)));
+    }
+    entry.events = dates_for(commit, settings)?.len() + celebrations_for(commit, settings).len();
+    if entry.events == 0 {
+        return Ok(entry.excluded(Reason::NoEvents, None));
+    }
+    entry.included = true;
+    Ok(entry)
+}
+
+/// Entries with their reasons in the user's language, for the HTML template.
+pub fn rows(entries: &[Entry], lang: &str) -> Result<Vec<liquid::Object>> {
+    entries
+        .iter()
+        .map(|entry| {
+            let explanation = entry.reason.map(|reason| {
+                let mut args = FluentArgs::new();
+                args.set("detail", entry.detail.clone().unwrap_or_default());
+                I18N.message(lang, reason.message_id(), Some(&args))
+            });
+            let mut row = liquid::model::to_object(entry)?;
+            row.insert("explanation".into(), liquid::model::to_value(&explanation)?);
+            Ok(row)
+        })
+        .collect()
+}
@@ -0,0 +1,454 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::calendar::validate_template;
+use crate::filters::{self, Filter};
+use crate::i18n;
+use crate::models::{FirstCommit, LeapDay, Occasion};
+use crate::templates::parse_user_template;
+
+const MAX_WINDOW: u16 = 100;
+const MAX_REMINDERS: usize = 5;
+const MAX_MILESTONES: usize = 50;
+const MAX_FEED_NAME_LENGTH: usize = 32;
+
+/// Query string parameters accepted by the calendar feeds.
+#[derive(Debug, Default, Deserialize)]
+pub struct Params {
+    past: Option<String>,
+    future: Option<String>,
+    remind: Option<String>,
+    remind_milestones: Option<String>,
+    leap_day: Option<String>,
+    milestones: Option<String>,
+    min_age: Option<String>,
+    min_stars: Option<String>,
+    min_commits: Option<String>,
+    include: Option<String>,
+    exclude: Option<String>,
+    lang: Option<String>,
+    summary: Option<String>,
+    description: Option<String>,
+    failures: Option<String>,
+    day_counts: Option<String>,
+    binary_birthdays: Option<String>,
+    half_birthdays: Option<String>,
+    monthsaries: Option<String>,
+    first_release: Option<String>,
+    stable_release: Option<String>,
+    star_milestones: Option<String>,
+    firsts: Option<String>,
+}
+
+/// How many years before and after the current one to materialize events for.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Window {
+    pub past: u16,
+    pub future: u16,
+}
+
+/// When to trigger an alarm, relative to the (all day) birthday event.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub enum Reminder {
+    /// Minutes before the day starts.
+    Before(i64),
+    /// Minutes after midnight on the day itself.
+    At(i64),
+}
+
+impl std::str::FromStr for Reminder {
+    type Err = anyhow::Error;
+
+    /// Parses `1w`, `2d`, `3h` or `30m` as an offset before the day, and `09:00` as a time on
+    /// the day itself.
+    fn from_str(value: &str) -> Result<Self> {
+        if let Some((hours, minutes)) = value.split_once(':') {
+            let hours = hours.parse::<i64>().ok().filter(|h| (0..24).contains(h));
+            let minutes = minutes.parse::<i64>().ok().filter(|m| (0..60).contains(m));
+            return match (hours, minutes) {
+                (Some(h), Some(m)) => Ok(Reminder::At(h * 60 + m)),
+                _ => Err(anyhow!("Invalid time of the day for a reminder: {}", value)),
+            };
+        }
+        if value == "0" {
+            return Ok(Reminder::Before(0));
+        }
+        let last = value.char_indices().last().map_or(0, |(i, _)| i);
+        let (amount, unit) = value.split_at(last);
+        let multiplier = match unit {
+            "m" => 1,
+            "h" => 60,
+            "d" => 60 * 24,
+            "w" => 60 * 24 * 7,
+            _ => return Err(anyhow!("Invalid unit for a reminder: {}", value)),
+        };
+        match amount.parse::<u16>() {
+            Ok(n) => Ok(Reminder::Before(i64::from(n) * multiplier)),
+            Err(e) => Err(anyhow!("Invalid offset for a reminder, {}: {}", value, e)),
+        }
+    }
+}
+
+/// Which ages count as milestones.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub enum Milestones {
+    /// The first birthday and every fifth one.
+    Default,
+    Custom(Vec<i32>),
+}
+
+impl Milestones {
+    pub fn contains(&self, age: i32) -> bool {
+        match self {
+            Milestones::Default => age == 1 || (age > 0 && age % 5 == 0),
+            Milestones::Custom(ages) => ages.contains(&age),
+        }
+    }
+}
+
+/// Options that change how a calendar feed is rendered.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct Settings {
+    pub window: Option<Window>,
+    pub reminders: Vec<Reminder>,
+    pub milestone_reminders: Vec<Reminder>,
+    pub leap_day: LeapDay,
+    /// When set, only events for these ages are kept.
+    pub milestones: Option<Milestones>,
+    pub min_age: Option<i32>,
+    pub min_stars: Option<u32>,
+    pub min_commits: Option<u32>,
+    /// When not empty, only repos matching at least one of these are kept.
+    pub include: Vec<Filter>,
+    pub exclude: Vec<Filter>,
+    /// When not set, the user's preferred language is used.
+    pub lang: Option<String>,
+    /// Liquid templates replacing the default event summary and description.
+    pub summary_template: Option<String>,
+    pub description_template: Option<String>,
+    /// Whether repos that could not be read are listed as X-properties of the calendar.
+    pub failures: bool,
+    /// Celebrations added to the yearly anniversaries, not affected by the age options.
+    pub occasions: Vec<Occasion>,
+}
+
+/// Blank parameters (e.g. empty form fields) are the same as missing ones.
+fn value(param: &Option<String>) -> Option<&String> {
+    param.as_ref().filter(|v| !v.trim().is_empty())
+}
+
+/// Feed names are part of their URL, so they are limited to lowercase letters, digits, `-` and `_`.
+pub fn validate_name(name: &str) -> Result<()> {
+    if name.is_empty() || name.len() > MAX_FEED_NAME_LENGTH {
+        return Err(anyhow!(
+            "Feed names must have between 1 and {} characters",
+            MAX_FEED_NAME_LENGTH
+        ));
+    }
+    let allowed = |c: char| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_';
+    if let Some(c) = name.chars().find(|c| !allowed(*c)) {
+        return Err(anyhow!("Invalid character {:?} in feed name {}", c, name));
+    }
+    Ok(())
+}
+
+fn parse_years(name: &str, value: Option<&String>) -> Result<u16> {
+    match value {
+        None => Ok(0),
+        Some(v) => {
+            let years = v
+                .parse::<u16>()
+                .map_err(|e| anyhow!("Invalid value for {}, {}: {}", name, v, e))?;
+            if years > MAX_WINDOW {
+                return Err(anyhow!(
+                    "Invalid value for {}, {}: must be {} or less",
+                    name,
+                    v,
+                    MAX_WINDOW
+                ));
+            }
+            Ok(years)
+        }
+    }
+}
+
+fn parse_reminders(name: &str, value: Option<&String>) -> Result<Vec<Reminder>> {
+    let reminders = match value {
+        None => return Ok(vec![]),
+        Some(v) => v
+            .split(',')
+            .map(|r| r.trim().parse::<Reminder>())
+            .collect::<Result<Vec<_>>>()
+            .map_err(|e| anyhow!("Invalid value for {}: {}", name, e))?,
+    };
+    if reminders.len() > MAX_REMINDERS {
+        return Err(anyhow!(
+            "Invalid value for {}: no more than {} reminders are allowed",
+            name,
+            MAX_REMINDERS
+        ));
+    }
+    Ok(reminders)
+}
+
+pub fn parse_leap_day(value: Option<&String>) -> Result<LeapDay> {
+    match value.map(|v| v.as_str()) {
+        None => Ok(LeapDay::default()),
+        Some("feb28") => Ok(LeapDay::February28),
+        Some("mar1") => Ok(LeapDay::March1),
+        Some("leap") => Ok(LeapDay::LeapYearsOnly),
+        Some(v) => Err(anyhow!(
+            "Invalid value for leap_day, {}: use feb28, mar1 or leap",
+            v
+        )),
+    }
+}
+
+/// The query string value of a leap day policy, the opposite of `parse_leap_day`.
+pub fn leap_day_param(policy: LeapDay) -> &'static str {
+    match policy {
+        LeapDay::February28 => "feb28",
+        LeapDay::March1 => "mar1",
+        LeapDay::LeapYearsOnly => "leap",
+    }
+}
+
+fn parse_number<T>(name: &str, value: Option<&String>) -> Result<Option<T>>
+where
+    T: std::str::FromStr,
+    T::Err: std::fmt::Display,
+{
+    value
+        .map(|v| {
+            v.parse::<T>()
+                .map_err(|e| anyhow!("Invalid value for {}, {}: {}", name, v, e))
+        })
+        .transpose()
+}
+
+fn parse_flag(name: &str, value: Option<&String>) -> Result<bool> {
+    match value.map(|v| v.as_str()) {
+        None | Some("false") => Ok(false),
+        Some("true") => Ok(true),
+        Some(v) => Err(anyhow!(
+            "Invalid value for {}, {}: use true or false",
+            name,
+            v
+        )),
+    }
+}
+
+fn parse_milestones(value: Option<&String>) -> Result<Option<Milestones>> {
+    match value.map(|v| v.as_str()) {
+        None | Some("false") => Ok(None),
+        Some("true") => Ok(Some(Milestones::Default)),
+        Some(v) => {
+            let ages = v
+                .split(',')
+                .map(|age| age.trim().parse::<u16>().map(i32::from))
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| anyhow!("Invalid value for milestones, {}: {}", v, e))?;
+            if ages.len() > MAX_MILESTONES {
+                return Err(anyhow!(
+                    "Invalid value for milestones: no more than {} ages are allowed",
+                    MAX_MILESTONES
+                ));
+            }
+            Ok(Some(Milestones::Custom(ages)))
+        }
+    }
+}
+
+/// Validates a language against the supported ones, returning its canonical tag.
+pub fn parse_lang(value: Option<&String>) -> Result<Option<String>> {
+    match value {
+        None => Ok(None),
+        Some(v) => match i18n::supported(v) {
+            Some(lang) => Ok(Some(lang.to_string())),
+            None => Err(anyhow!("Unsupported value for lang: {}", v)),
+        },
+    }
+}
+
+fn parse_template(name: &str, value: Option<&String>) -> Result<Option<String>> {
+    match value {
+        None => Ok(None),
+        Some(v) => {
+            parse_user_template(v).map_err(|e| anyhow!("Invalid template for {}: {}", name, e))?;
+            Ok(Some(v.clone()))
+        }
+    }
+}
+
+impl TryFrom<Params> for Settings {
+    type Error = anyhow::Error;
+
+    fn try_from(params: Params) -> Result<Self> {
+        let mut settings = Settings::default();
+        if value(&params.past).is_some() || value(&params.future).is_some() {
+            settings.window = Some(Window {
+                past: parse_years("past", value(&params.past))?,
+                future: parse_years("future", value(&params.future))?,
+            });
+        }
+        settings.reminders = parse_reminders("remind", value(&params.remind))?;
+        settings.milestone_reminders =
+            parse_reminders("remind_milestones", value(&params.remind_milestones))?;
+        settings.leap_day = parse_leap_day(value(&params.leap_day))?;
+        settings.milestones = parse_milestones(value(&params.milestones))?;
+        settings.min_age = parse_number::<u16>("min_age", value(&params.min_age))?.map(i32::from);
+        settings.min_stars = parse_number("min_stars", value(&params.min_stars))?;
+        settings.min_commits = parse_number("min_commits", value(&params.min_commits))?;
+        settings.include = filters::parse("include", value(&params.include))?;
+        settings.exclude = filters::parse("exclude", value(&params.exclude))?;
+        settings.lang = parse_lang(value(&params.lang))?;
+        settings.summary_template = parse_template("summary", value(&params.summary))?;
+        settings.description_template = parse_template("description", value(&params.description))?;
+        settings.failures = parse_flag("failures", value(&params.failures))?;
+        if parse_flag("firsts", value(&params.firsts))? {
+            settings.occasions.extend([
+                Occasion::FirstIssue,
+                Occasion::FirstPullRequest,
+                Occasion::FirstContribution,
+                Occasion::FirstFork,
+            ]);
+        }
+        for (name, param, occasion) in [
+            ("day_counts", &params.day_counts, Occasion::DayCount),
+            (
+                "binary_birthdays",
+                &params.binary_birthdays,
+                Occasion::PowerOfTwo,
+            ),
+            (
+                "half_birthdays",
+                &params.half_birthdays,
+                Occasion::HalfBirthday,
+            ),
+            ("monthsaries", &params.monthsaries, Occasion::Monthsary),
+            (
+                "first_release",
+                &params.first_release,
+                Occasion::FirstRelease,
+            ),
+            (
+                "stable_release",
+                &params.stable_release,
+                Occasion::StableRelease,
+            ),
+            (
+                "star_milestones",
+                &params.star_milestones,
+                Occasion::StarMilestone,
+            ),
+        ] {
+            if parse_flag(name, value(param))? {
+                settings.occasions.push(occasion);
+            }
+        }
+        Ok(settings)
+    }
+}
+
+impl Settings {
+    /// Renders the templates against a sample repository. Unlike parsing, rendering takes time, so
+    /// it only happens once the visitor is allowed to see the calendar.
+    pub fn validate_templates(&self) -> Result<()> {
+        for (name, template) in [
+            ("summary", &self.summary_template),
+            ("description", &self.description_template),
+        ] {
+            if let Some(template) = template {
+                validate_template(template)
+                    .map_err(|e| anyhow!("Invalid template for {}: {}", name, e))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Identifies this combination of settings in the cache, `None` being the default feed.
+    pub fn variant(&self) -> Option<String> {
+        if *self == Settings::default() {
+            return None;
+        }
+        let json = serde_json::to_string(self).unwrap_or_default();
+        Some(format!("{:x}", Sha256::digest(json.as_bytes())))
+    }
+
+    /// Ages considered milestones, the default ones unless the feed customizes them.
+    pub fn is_milestone(&self, age: i32) -> bool {
+        self.milestones
+            .as_ref()
+            .unwrap_or(&Milestones::Default)
+            .contains(age)
+    }
+
+    /// Whether a repo is kept at all, regardless of its age.
+    pub fn keeps(&self, commit: &FirstCommit) -> bool {
+        self.filtered_by(commit).is_none()
+    }
+
+    /// The option leaving a repo out, if any, as it is written in the URL.
+    pub fn filtered_by(&self, commit: &FirstCommit) -> Option<String> {
+        if let Some(min) = self.min_stars.filter(|min| commit.stars < *min) {
+            return Some(format!("min_stars={}", min));
+        }
+        if let Some(min) = self.min_commits.filter(|min| commit.commits < *min) {
+            return Some(format!("min_commits={}", min));
+        }
+        if !self.include.is_empty() && !self.include.iter().any(|f| f.matches(commit)) {
+            let include: Vec<String> = self.include.iter().map(|f| f.to_string()).collect();
+            return Some(format!("include={}", include.join(",")));
+        }
+        self.exclude
+            .iter()
+            .find(|f| f.matches(commit))
+            .map(|f| format!("exclude={}", f))
+    }
+
+    /// Whether the celebration of a given age is kept.
+    pub fn keeps_age(&self, age: i32) -> bool {
+        self.min_age.is_none_or(|min| age >= min)
+            && self.milestones.as_ref().is_none_or(|m| m.contains(age))
+    }
+
+    /// Reminders for an event celebrating the given age.
+    pub fn reminders_for(&self, age: i32) -> &[Reminder] {
+        if self.is_milestone(age) && !self.milestone_reminders.is_empty() {
+            return &self.milestone_reminders;
+        }
+        &self.reminders
+    }
+}
+
+fn enabled() -> bool {
+    true
+}
+
+/// User-wide preferences, as opposed to the settings of each feed.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Preferences {
+    /// Whether calendars are also served from URLs that do not include the user's secret.
+    #[serde(default = "enabled")]
+    pub public: bool,
+
+    /// Language for feeds that do not set one.
+    #[serde(default)]
+    pub lang: Option<String>,
+
+    /// When repos born on February 29th are counted as a year older on the user's page.
+    #[serde(default)]
+    pub leap_day: LeapDay,
+}
+
+impl Default for Preferences {
+    fn default() -> Self {
+        Self {
+            public: enabled(),
+            lang: None,
+            leap_day: LeapDay::default(),
+        }
+    }
+}
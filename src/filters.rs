@@ -0,0 +1,138 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::models::FirstCommit;
+
+const MAX_FILTERS: usize = 20;
+const MAX_PATTERN_LENGTH: usize = 100;
+
+/// What part of a repository a pattern is matched against.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub enum Field {
+    Repository,
+    Topic,
+    Language,
+}
+
+/// A glob pattern (`*` for any sequence of characters, `?` for any single character) matched,
+/// case-insensitively, against one field of the repository.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Filter {
+    pub field: Field,
+    pub pattern: String,
+}
+
+fn glob(pattern: &[char], text: &[char]) -> bool {
+    let (mut p, mut t) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, t));
+            p += 1;
+        } else if let Some((star_p, star_t)) = star {
+            p = star_p + 1;
+            t = star_t + 1;
+            star = Some((star_p, star_t + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+impl std::str::FromStr for Filter {
+    type Err = anyhow::Error;
+
+    /// Parses `owner/name` globs, or `topic:` and `language:` prefixed globs.
+    fn from_str(value: &str) -> Result<Self> {
+        let (field, pattern) = match value.split_once(':') {
+            None => (Field::Repository, value),
+            Some(("topic", pattern)) => (Field::Topic, pattern),
+            Some(("language", pattern)) => (Field::Language, pattern),
+            Some((prefix, _)) => {
+                return Err(anyhow!(
+                "Unknown filter prefix {}: use topic: or language:, or no prefix for owner/name",
+                prefix
+            ))
+            }
+        };
+        if pattern.is_empty() {
+            return Err(anyhow!("Empty pattern in filter {}", value));
+        }
+        if pattern.len() > MAX_PATTERN_LENGTH {
+            return Err(anyhow!(
+                "Pattern in filter {} is longer than {} characters",
+                value,
+                MAX_PATTERN_LENGTH
+            ));
+        }
+        if field == Field::Repository && !pattern.contains('/') {
+            return Err(anyhow!(
+                "Repository filter {} must match owner/name (e.g. {}/* or */{})",
+                value,
+                pattern,
+                pattern
+            ));
+        }
+        let allowed = |c: char| c.is_alphanumeric() || "-_./*?+# ".contains(c);
+        if let Some(c) = pattern.chars().find(|c| !allowed(*c)) {
+            return Err(anyhow!("Invalid character {:?} in filter {}", c, value));
+        }
+        Ok(Self {
+            field,
+            pattern: pattern.to_lowercase(),
+        })
+    }
+}
+
+impl Filter {
+    fn matches_text(&self, text: &str) -> bool {
+        let pattern: Vec<char> = self.pattern.chars().collect();
+        let text: Vec<char> = text.to_lowercase().chars().collect();
+        glob(&pattern, &text)
+    }
+
+    pub fn matches(&self, commit: &FirstCommit) -> bool {
+        match self.field {
+            Field::Repository => self.matches_text(&format!("{}/{}", commit.owner, commit.name)),
+            Field::Topic => commit.topics.iter().any(|t| self.matches_text(t)),
+            Field::Language => commit
+                .language
+                .as_ref()
+                .is_some_and(|l| self.matches_text(l)),
+        }
+    }
+}
+
+impl std::fmt::Display for Filter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.field {
+            Field::Repository => write!(f, "{}", self.pattern),
+            Field::Topic => write!(f, "topic:{}", self.pattern),
+            Field::Language => write!(f, "language:{}", self.pattern),
+        }
+    }
+}
+
+/// Parses a comma-separated list of filters.
+pub fn parse(name: &str, value: Option<&String>) -> Result<Vec<Filter>> {
+    let filters = match value {
+        None => return Ok(vec![]),
+        Some(v) => v
+            .split(',')
+            .map(|f| f.trim().parse::<Filter>())
+            .collect::<Result<Vec<_>>>()
+            .map_err(|e| anyhow!("Invalid value for {}: {}", name, e))?,
+    };
+    if filters.len() > MAX_FILTERS {
+        return Err(anyhow!(
+            "Invalid value for {}: no more than {} filters are allowed",
+            name,
+            MAX_FILTERS
+        ));
+    }
+    Ok(filters)
+}
@@ -0,0 +1,258 @@
+use anyhow::Result;
+use chrono::NaiveDateTime;
+use serde::Deserialize;
+
+use crate::date_time_serializer;
+use crate::graphql::GitHubGraphQL;
+use crate::i18n::I18N;
+use crate::models::{First, FirstCommit, Firsts};
+
+// the oldest merged pull requests are paged through, 100 at a time, until an outside one shows up
+const MAX_MERGED_PAGES: usize = 10;
+// GitHub's association of the author with the repository when it is not an outside contributor
+const INSIDE_ASSOCIATIONS: [&str; 3] = ["OWNER", "MEMBER", "COLLABORATOR"];
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Author {
+    pub login: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Item {
+    pub title: String,
+    pub url: String,
+    #[serde(with = "date_time_serializer")]
+    pub created_at: NaiveDateTime,
+    pub author: Option<Author>,
+}
+
+impl Item {
+    fn as_first(&self) -> First {
+        First {
+            title: self.title.clone(),
+            url: self.url.clone(),
+            date: self.created_at,
+            author: self.author.as_ref().map(|author| author.login.clone()),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MergedPullRequest {
+    pub title: String,
+    pub url: String,
+    #[serde(with = "date_time_serializer")]
+    pub merged_at: NaiveDateTime,
+    pub author_association: String,
+    pub author: Option<Author>,
+}
+
+impl MergedPullRequest {
+    fn is_outside(&self) -> bool {
+        !INSIDE_ASSOCIATIONS.contains(&self.author_association.as_str())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PageInfo {
+    pub has_next_page: bool,
+    pub end_cursor: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Merged {
+    pub nodes: Vec<MergedPullRequest>,
+    pub page_info: PageInfo,
+}
+
+impl Merged {
+    /// Among these merged pull requests, the first one merged from someone who is not the owner,
+    /// a member of the organization or a collaborator.
+    fn contribution(&self) -> Option<First> {
+        self.nodes
+            .iter()
+            .filter(|pr| pr.is_outside())
+            .min_by_key(|pr| pr.merged_at)
+            .map(|pr| First {
+                title: pr.title.clone(),
+                url: pr.url.clone(),
+                date: pr.merged_at,
+                author: pr.author.as_ref().map(|author| author.login.clone()),
+            })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Fork {
+    pub name_with_owner: String,
+    pub url: String,
+    #[serde(with = "date_time_serializer")]
+    pub created_at: NaiveDateTime,
+    pub owner: Author,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Nodes<T> {
+    pub nodes: Vec<T>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Repository {
+    pub issues: Nodes<Item>,
+    pub pull_requests: Nodes<Item>,
+    pub merged: Merged,
+    pub forks: Nodes<Fork>,
+}
+
+impl Repository {
+    fn firsts(&self) -> Firsts {
+        Firsts {
+            issue: self.issues.nodes.first().map(Item::as_first),
+            pull_request: self.pull_requests.nodes.first().map(Item::as_first),
+            contribution: self.merged.contribution(),
+            fork: self.forks.nodes.first().map(|fork| First {
+                title: fork.name_with_owner.clone(),
+                url: fork.url.clone(),
+                date: fork.created_at,
+                author: Some(fork.owner.login.clone()),
+            }),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Data {
+    pub repository: Repository,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Response {
+    pub data: Data,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MergedRepository {
+    pub merged: Merged,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MergedData {
+    pub repository: MergedRepository,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MergedResponse {
+    pub data: MergedData,
+}
+
+/// Keeps paging through merged pull requests when the first page has no outside contribution.
+async fn contribution(
+    client: &GitHubGraphQL,
+    owner: &str,
+    name: &str,
+    mut page_info: PageInfo,
+) -> Result<Option<First>> {
+    for _ in 1..MAX_MERGED_PAGES {
+        if !page_info.has_next_page {
+            break;
+        }
+        let cursor = page_info.end_cursor.unwrap_or_default();
+        let resp = client.merged(name, owner, &cursor).await?;
+        let contents: MergedResponse = serde_json::from_str(&resp).map_err(|e| {
+            anyhow::anyhow!(
+                "Error parsing merged pull requests of {owner}/{name}: {}\n{}",
+                e,
+                resp
+            )
+        })?;
+        let merged = contents.data.repository.merged;
+        if let Some(first) = merged.contribution() {
+            return Ok(Some(first));
+        }
+        page_info = merged.page_info;
+    }
+    Ok(None)
+}
+
+/// The oldest issue, pull request, merged contribution and fork of a repo.
+pub async fn firsts(client: &GitHubGraphQL, owner: &str, name: &str) -> Result<Firsts> {
+    let resp = client.firsts(name, owner).await?;
+    let contents: Response = serde_json::from_str(&resp)
+        .map_err(|e| anyhow::anyhow!("Error parsing firsts of {owner}/{name}: {}\n{}", e, resp))?;
+    let repository = contents.data.repository;
+    let mut firsts = repository.firsts();
+    if firsts.contribution.is_none() {
+        firsts.contribution =
+            contribution(client, owner, name, repository.merged.page_info).await?;
+    }
+    Ok(firsts)
+}
+
+/// Everything a repo remembers, oldest first, for the HTML template.
+pub fn timeline(commit: &FirstCommit, lang: &str) -> Vec<liquid::Object> {
+    let mut events = vec![(
+        commit.date,
+        "timeline-first-commit",
+        commit.headline().to_string(),
+        commit.commit_url.clone(),
+        commit.author.clone(),
+    )];
+    for (release, id) in [
+        (&commit.first_release, "timeline-first-release"),
+        (&commit.stable_release, "timeline-stable-release"),
+    ] {
+        if let Some(release) = release {
+            events.push((
+                release.date,
+                id,
+                release.tag.clone(),
+                release.url.clone(),
+                None,
+            ));
+        }
+    }
+    for milestone in &commit.star_milestones {
+        events.push((
+            milestone.date,
+            "timeline-star-milestone",
+            format!("★ {}", milestone.stars),
+            format!("{}/stargazers", commit.url),
+            None,
+        ));
+    }
+    for (first, id) in [
+        (&commit.firsts.issue, "timeline-first-issue"),
+        (&commit.firsts.pull_request, "timeline-first-pull-request"),
+        (&commit.firsts.contribution, "timeline-first-contribution"),
+        (&commit.firsts.fork, "timeline-first-fork"),
+    ] {
+        if let Some(first) = first {
+            events.push((
+                first.date,
+                id,
+                first.title.clone(),
+                first.url.clone(),
+                first.author.clone(),
+            ));
+        }
+    }
+    events.sort_by_key(|(date, ..)| *date);
+    events
+        .into_iter()
+        .map(|(date, id, title, url, author)| {
+            liquid::object!({
+                "date": date.format("%Y-%m-%d").to_string(),
+                "label": I18N.message(lang, id, None),
+                "title": title,
+                "url": url,
+                "author": author,
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,288 @@
+use anyhow::{anyhow, Result};
+use serde_json::{json, Value};
+
+/// Representations of the same calendar: iCalendar, jCal (RFC 7265) and xCal (RFC 6321).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Format {
+    ICalendar,
+    JCal,
+    XCal,
+}
+
+impl Format {
+    /// Picks the format from the URL extension, then from the `Accept` header.
+    pub fn negotiate(extension: &str, accept: &str) -> Self {
+        match extension {
+            "jcal" => Self::JCal,
+            "xcal" => Self::XCal,
+            _ if accept.contains("application/calendar+json") => Self::JCal,
+            _ if accept.contains("application/calendar+xml") => Self::XCal,
+            _ => Self::ICalendar,
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::ICalendar => "text/calendar",
+            Self::JCal => "application/calendar+json",
+            Self::XCal => "application/calendar+xml",
+        }
+    }
+
+    /// Converts an iCalendar document to this format.
+    pub fn render(&self, ics: String) -> Result<String> {
+        match self {
+            Self::ICalendar => Ok(ics),
+            Self::JCal => Ok(jcal(&parse(&ics)?).to_string()),
+            Self::XCal => Ok(xcal(&parse(&ics)?)),
+        }
+    }
+}
+
+struct Property {
+    name: String,
+    params: Vec<(String, String)>,
+    value_type: &'static str,
+    value: String,
+}
+
+struct Component {
+    name: String,
+    properties: Vec<Property>,
+    components: Vec<Component>,
+}
+
+fn unfold(ics: &str) -> String {
+    ics.replace("\r\n ", "")
+        .replace("\r\n\t", "")
+        .replace("\n ", "")
+        .replace("\n\t", "")
+}
+
+fn unescape(value: &str) -> String {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') | Some('N') => unescaped.push('\n'),
+            Some(c) if "\\,;:".contains(c) => unescaped.push(c),
+            Some(c) => {
+                unescaped.push('\\');
+                unescaped.push(c);
+            }
+            None => unescaped.push('\\'),
+        }
+    }
+    unescaped
+}
+
+// value types of the properties our calendars use, as named by RFC 7265 and RFC 6321
+fn default_value_type(name: &str) -> &'static str {
+    match name {
+        "DTSTART" | "DTEND" | "DTSTAMP" | "CREATED" | "LAST-MODIFIED" => "date-time",
+        "TRIGGER" | "DURATION" => "duration",
+        "URL" | "TZURL" | "ATTACH" => "uri",
+        "SEQUENCE" | "PRIORITY" | "REPEAT" => "integer",
+        "UID" | "SUMMARY" | "DESCRIPTION" | "LOCATION" | "COMMENT" | "CATEGORIES" | "CLASS"
+        | "STATUS" | "TRANSP" | "ACTION" | "PRODID" | "VERSION" | "CALSCALE" | "METHOD"
+        | "NAME" => "text",
+        _ if name.starts_with("X-WR-") => "text",
+        _ => "unknown",
+    }
+}
+
+fn property(line: &str) -> Result<Property> {
+    let mut parts = vec![];
+    let mut current = String::new();
+    let mut quoted = false;
+    let mut rest = None;
+    for (index, c) in line.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            ';' if !quoted => parts.push(std::mem::take(&mut current)),
+            ':' if !quoted => {
+                parts.push(std::mem::take(&mut current));
+                rest = Some(&line[index + 1..]);
+                break;
+            }
+            _ => current.push(c),
+        }
+    }
+    let value = rest.ok_or_else(|| anyhow!("Invalid iCalendar line: {}", line))?;
+    let mut parts = parts.into_iter();
+    let name = parts.next().unwrap_or_default().to_uppercase();
+    let mut value_type = default_value_type(&name);
+    let mut params = vec![];
+    for part in parts {
+        let (key, param) = part
+            .split_once('=')
+            .ok_or_else(|| anyhow!("Invalid parameter in iCalendar line: {}", line))?;
+        if key.eq_ignore_ascii_case("VALUE") {
+            value_type = match param.to_uppercase().as_str() {
+                "DATE" => "date",
+                "DATE-TIME" => "date-time",
+                "DURATION" => "duration",
+                "URI" => "uri",
+                "INTEGER" => "integer",
+                "TEXT" => "text",
+                _ => "unknown",
+            };
+        } else {
+            params.push((key.to_lowercase(), param.to_string()));
+        }
+    }
+    let value = match value_type {
+        "text" => unescape(value),
+        _ => value.to_string(),
+    };
+    Ok(Property {
+        name,
+        params,
+        value_type,
+        value,
+    })
+}
+
+fn parse(ics: &str) -> Result<Component> {
+    let mut stack: Vec<Component> = vec![];
+    for line in unfold(ics).lines().filter(|line| !line.trim().is_empty()) {
+        if let Some(name) = line.strip_prefix("BEGIN:") {
+            stack.push(Component {
+                name: name.trim().to_uppercase(),
+                properties: vec![],
+                components: vec![],
+            });
+        } else if let Some(name) = line.strip_prefix("END:") {
+            let component = stack
+                .pop()
+                .filter(|component| component.name == name.trim().to_uppercase())
+                .ok_or_else(|| anyhow!("Unexpected END:{} in iCalendar", name))?;
+            match stack.last_mut() {
+                Some(parent) => parent.components.push(component),
+                None => return Ok(component),
+            }
+        } else {
+            stack
+                .last_mut()
+                .ok_or_else(|| anyhow!("Property outside of a component: {}", line))?
+                .properties
+                .push(property(line)?);
+        }
+    }
+    Err(anyhow!("Incomplete iCalendar document"))
+}
+
+// iCalendar uses basic formats (20261019T090000Z), jCal and xCal use extended ones
+fn extended(property: &Property) -> String {
+    let value = property.value.as_str();
+    let date = |v: &str| match (v.get(..4), v.get(4..6), v.get(6..8)) {
+        (Some(y), Some(m), Some(d)) if v.len() == 8 => format!("{}-{}-{}", y, m, d),
+        _ => v.to_string(),
+    };
+    match property.value_type {
+        "date" => date(value),
+        "date-time" => match value.split_once('T') {
+            Some((day, time)) if time.len() >= 6 => format!(
+                "{}T{}:{}:{}",
+                date(day),
+                &time[..2],
+                &time[2..4],
+                &time[4..]
+            ),
+            _ => value.to_string(),
+        },
+        _ => value.to_string(),
+    }
+}
+
+fn jcal(component: &Component) -> Value {
+    let properties: Vec<Value> = component
+        .properties
+        .iter()
+        .map(|property| {
+            let params: serde_json::Map<String, Value> = property
+                .params
+                .iter()
+                .map(|(key, value)| (key.clone(), json!(value.trim_matches('"'))))
+                .collect();
+            let value = match property.value_type {
+                "integer" => property
+                    .value
+                    .parse::<i64>()
+                    .map_or_else(|_| json!(property.value), |n| json!(n)),
+                _ => json!(extended(property)),
+            };
+            json!([
+                property.name.to_lowercase(),
+                params,
+                property.value_type,
+                value
+            ])
+        })
+        .collect();
+    let components: Vec<Value> = component.components.iter().map(jcal).collect();
+    json!([component.name.to_lowercase(), properties, components])
+}
+
+/// XML 1.0 has no way to represent control characters other than tab, LF and CR, so they are
+/// replaced.
+fn escape(text: &str) -> String {
+    text.replace(
+        |c: char| c < '\u{20}' && !matches!(c, '\t' | '\n' | '\r'),
+        "\u{FFFD}",
+    )
+    .replace('&', "&amp;")
+    .replace('<', "&lt;")
+    .replace('>', "&gt;")
+    .replace('"', "&quot;")
+}
+
+fn xcal_component(component: &Component, xml: &mut String) {
+    let name = component.name.to_lowercase();
+    xml.push_str(&format!("<{}><properties>", name));
+    for property in &component.properties {
+        let key = property.name.to_lowercase();
+        xml.push_str(&format!("<{}>", key));
+        if !property.params.is_empty() {
+            xml.push_str("<parameters>");
+            for (param, value) in &property.params {
+                xml.push_str(&format!(
+                    "<{}><text>{}</text></{}>",
+                    param,
+                    escape(value.trim_matches('"')),
+                    param
+                ));
+            }
+            xml.push_str("</parameters>");
+        }
+        xml.push_str(&format!(
+            "<{}>{}</{}>",
+            property.value_type,
+            escape(&extended(property)),
+            property.value_type
+        ));
+        xml.push_str(&format!("</{}>", key));
+    }
+    xml.push_str("</properties>");
+    if !component.components.is_empty() {
+        xml.push_str("<components>");
+        for child in &component.components {
+            xcal_component(child, xml);
+        }
+        xml.push_str("</components>");
+    }
+    xml.push_str(&format!("</{}>", name));
+}
+
+fn xcal(component: &Component) -> String {
+    let mut xml = String::from(
+        r#"<?xml version="1.0" encoding="utf-8"?><icalendar xmlns="urn:ietf:params:xml:ns:icalendar-2.0">"#,
+    );
+    xcal_component(component, &mut xml);
+    xml.push_str("</icalendar>");
+    xml
+}
@@ -54,10 +54,11 @@ impl GitHubGraphQL {
         Ok(resp)
     }
 
-    pub async fn repos(&self, username: &str, cursor: &str) -> Result<String> {
+    pub async fn repos(&self, username: &str, cursor: &str, public_only: bool) -> Result<String> {
         let context = liquid::object!({
             "username": username,
             "cursor": cursor,
+            "public_only": public_only,
         });
         let query = TEMPLATES.graphql.repos.render(&context)?;
         let resp = self.request(query).await?;
@@ -87,4 +88,82 @@ impl GitHubGraphQL {
 
         Ok(resp.text().await?)
     }
+
+    pub async fn releases(&self, name: &str, owner: &str, cursor: &str) -> Result<String> {
+        let context = liquid::object!({
+            "name": name,
+            "owner": owner,
+            "cursor": cursor,
+        });
+        let query = TEMPLATES.graphql.releases.render(&context)?;
+        let resp = self.request(query).await?;
+
+        Ok(resp.text().await?)
+    }
+
+    pub async fn stargazers(&self, name: &str, owner: &str, cursor: &str) -> Result<String> {
+        let context = liquid::object!({
+            "name": name,
+            "owner": owner,
+            "cursor": cursor,
+        });
+        let query = TEMPLATES.graphql.stargazers.render(&context)?;
+        let resp = self.request(query).await?;
+
+        Ok(resp.text().await?)
+    }
+
+    pub async fn repository(&self, name: &str, owner: &str) -> Result<String> {
+        let context = liquid::object!({
+            "name": name,
+            "owner": owner,
+        });
+        let query = TEMPLATES.graphql.repository.render(&context)?;
+        let resp = self.request(query).await?;
+
+        Ok(resp.text().await?)
+    }
+
+    pub async fn firsts(&self, name: &str, owner: &str) -> Result<String> {
+        let context = liquid::object!({
+            "name": name,
+            "owner": owner,
+        });
+        let query = TEMPLATES.graphql.firsts.render(&context)?;
+        let resp = self.request(query).await?;
+
+        Ok(resp.text().await?)
+    }
+
+    pub async fn merged(&self, name: &str, owner: &str, cursor: &str) -> Result<String> {
+        let context = liquid::object!({
+            "name": name,
+            "owner": owner,
+            "cursor": cursor,
+        });
+        let query = TEMPLATES.graphql.merged.render(&context)?;
+        let resp = self.request(query).await?;
+
+        Ok(resp.text().await?)
+    }
+
+    pub async fn org_repos(&self, org: &str, cursor: &str, public_only: bool) -> Result<String> {
+        let context = liquid::object!({
+            "org": org,
+            "cursor": cursor,
+            "public_only": public_only,
+        });
+        let query = TEMPLATES.graphql.org_repos.render(&context)?;
+        let resp = self.request(query).await?;
+
+        Ok(resp.text().await?)
+    }
+
+    pub async fn membership(&self, org: &str) -> Result<String> {
+        let context = liquid::object!({ "org": org });
+        let query = TEMPLATES.graphql.membership.render(&context)?;
+        let resp = self.request(query).await?;
+
+        Ok(resp.text().await?)
+    }
 }
@@ -0,0 +1,138 @@
+use anyhow::{anyhow, Result};
+use chrono::{Datelike, Local, NaiveDate};
+use std::collections::BTreeMap;
+
+use crate::i18n::I18N;
+use crate::models::{FirstCommit, LeapDay};
+
+const MIN_YEAR: i32 = 1970;
+const MAX_YEAR: i32 = 2999;
+
+pub fn validate_year(year: i32) -> Result<()> {
+    if !(MIN_YEAR..=MAX_YEAR).contains(&year) {
+        return Err(anyhow!(
+            "Invalid year {}: must be between {} and {}",
+            year,
+            MIN_YEAR,
+            MAX_YEAR
+        ));
+    }
+    Ok(())
+}
+
+pub fn validate_month(month: u32) -> Result<()> {
+    if !(1..=12).contains(&month) {
+        return Err(anyhow!("Invalid month {}: must be between 1 and 12", month));
+    }
+    Ok(())
+}
+
+/// Repos celebrating on each day of a year, using the same date math as the calendar feeds.
+fn birthdays_in(
+    commits: &[FirstCommit],
+    year: i32,
+) -> Result<BTreeMap<NaiveDate, Vec<&FirstCommit>>> {
+    let mut birthdays: BTreeMap<NaiveDate, Vec<&FirstCommit>> = BTreeMap::new();
+    for commit in commits {
+        if commit.age(year) < 1 {
+            continue;
+        }
+        if let Some(date) = commit.anniversary_in(year, LeapDay::default())? {
+            birthdays.entry(date).or_default().push(commit);
+        }
+    }
+    for repos in birthdays.values_mut() {
+        repos.sort_by(|a, b| (&a.owner, &a.name).cmp(&(&b.owner, &b.name)));
+    }
+    Ok(birthdays)
+}
+
+/// Weeks of a month, starting on Monday, with empty cells before the first and after the last day.
+fn month(
+    year: i32,
+    number: u32,
+    birthdays: &BTreeMap<NaiveDate, Vec<&FirstCommit>>,
+    lang: &str,
+) -> Result<liquid::Object> {
+    let first = NaiveDate::from_ymd_opt(year, number, 1)
+        .ok_or_else(|| anyhow!("Invalid month {}-{}", year, number))?;
+    let today = Local::now().date_naive();
+    let mut weeks = vec![];
+    let mut week: Vec<liquid::Object> = (0..first.weekday().num_days_from_monday())
+        .map(|_| liquid::Object::new())
+        .collect();
+    let mut total = 0;
+    for date in first.iter_days().take_while(|date| date.month() == number) {
+        let repos: Vec<liquid::Object> = birthdays
+            .get(&date)
+            .into_iter()
+            .flatten()
+            .map(|commit| {
+                liquid::object!({
+                    "owner": commit.owner,
+                    "name": commit.name,
+                    "url": commit.url,
+                    "age": commit.age(year),
+                })
+            })
+            .collect();
+        total += repos.len();
+        week.push(liquid::object!({
+            "day": date.day(),
+            "today": date == today,
+            "repos": repos,
+        }));
+        if week.len() == 7 {
+            weeks.push(std::mem::take(&mut week));
+        }
+    }
+    if !week.is_empty() {
+        week.resize_with(7, liquid::Object::new);
+        weeks.push(week);
+    }
+    Ok(liquid::object!({
+        "number": number,
+        "name": I18N.message(lang, &format!("month-{}", number), None),
+        "weeks": weeks,
+        "total": total,
+    }))
+}
+
+fn weekdays(lang: &str) -> Vec<String> {
+    (1..=7)
+        .map(|day| I18N.message(lang, &format!("weekday-{}", day), None))
+        .collect()
+}
+
+/// All months of a year, or a single one, for the HTML template.
+pub fn grid(
+    commits: &[FirstCommit],
+    year: i32,
+    only: Option<u32>,
+    lang: &str,
+) -> Result<liquid::Object> {
+    let birthdays = birthdays_in(commits, year)?;
+    let months = match only {
+        Some(number) => vec![month(year, number, &birthdays, lang)?],
+        None => (1..=12)
+            .map(|number| month(year, number, &birthdays, lang))
+            .collect::<Result<Vec<_>>>()?,
+    };
+    let (previous, next) = match only {
+        Some(12) => (format!("{}/11", year), format!("{}/1", year + 1)),
+        Some(1) => (format!("{}/12", year - 1), format!("{}/2", year)),
+        Some(number) => (
+            format!("{}/{}", year, number - 1),
+            format!("{}/{}", year, number + 1),
+        ),
+        None => ((year - 1).to_string(), (year + 1).to_string()),
+    };
+    Ok(liquid::object!({
+        "year": year,
+        "month": only,
+        "previous": previous,
+        "next": next,
+        "months": months,
+        "weekdays": weekdays(lang),
+    }))
+}
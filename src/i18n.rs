@@ -0,0 +1,206 @@
+use anyhow::{anyhow, Result};
+use fluent_bundle::{concurrent::FluentBundle, FluentArgs, FluentResource};
+use lazy_static::lazy_static;
+use unic_langid::LanguageIdentifier;
+
+pub const DEFAULT_LANGUAGE: &str = "en";
+
+const CATALOGS: [(&str, &str); 4] = [
+    ("en", include_str!("locales/en.ftl")),
+    ("pt-BR", include_str!("locales/pt-BR.ftl")),
+    ("de", include_str!("locales/de.ftl")),
+    ("ja", include_str!("locales/ja.ftl")),
+];
+
+// messages used by the HTML templates, available there as `t.<id>` (with `_` instead of `-`)
+const HTML_MESSAGES: [&str; 79] = [
+    "title",
+    "tagline",
+    "login-notice",
+    "login-button",
+    "add-url",
+    "private-only",
+    "private-url",
+    "copy-secret",
+    "private-feeds",
+    "has-secret",
+    "no-secret",
+    "generate-secret",
+    "regenerate-secret",
+    "public",
+    "language",
+    "preferred-language",
+    "save",
+    "feeds",
+    "feeds-intro",
+    "delete",
+    "name",
+    "leap-day",
+    "leap-mar1",
+    "leap-feb28",
+    "leap-only",
+    "past",
+    "future",
+    "remind",
+    "remind-milestones",
+    "milestones",
+    "min-age",
+    "min-stars",
+    "min-commits",
+    "include",
+    "exclude",
+    "summary-template",
+    "description-template",
+    "save-feed",
+    "upcoming",
+    "loading",
+    "search",
+    "filter",
+    "repository",
+    "born",
+    "age",
+    "countdown",
+    "headline",
+    "no-repos",
+    "calendar-view",
+    "previous",
+    "next",
+    "whole-year",
+    "repositories",
+    "errors",
+    "failed",
+    "failures",
+    "include-failures",
+    "explain",
+    "explain-intro",
+    "decision",
+    "included",
+    "occasions",
+    "day-counts",
+    "binary-birthdays",
+    "half-birthdays",
+    "monthsaries",
+    "first-release",
+    "stable-release",
+    "star-milestones",
+    "timeline",
+    "firsts",
+    "organizations",
+    "organizations-intro",
+    "org-login-button",
+    "register-org",
+    "org-public-url",
+    "org-private-url",
+    "org-public-only",
+    "open-source",
+];
+
+lazy_static! {
+    pub static ref I18N: Catalogs = Catalogs::new().expect("Error building message catalogs");
+}
+
+pub struct Catalogs {
+    bundles: Vec<(&'static str, FluentBundle<FluentResource>)>,
+}
+
+impl Catalogs {
+    fn new() -> Result<Self> {
+        let mut bundles = Vec::with_capacity(CATALOGS.len());
+        for (lang, source) in CATALOGS {
+            let id: LanguageIdentifier = lang.parse()?;
+            let resource = FluentResource::try_new(source.to_string())
+                .map_err(|(_, e)| anyhow!("Error parsing messages for {}: {:?}", lang, e))?;
+            let mut bundle = FluentBundle::new_concurrent(vec![id]);
+            bundle.set_use_isolating(false);
+            bundle
+                .add_builtins()
+                .map_err(|e| anyhow!("Error loading functions for {}: {:?}", lang, e))?;
+            bundle
+                .add_resource(resource)
+                .map_err(|e| anyhow!("Error loading messages for {}: {:?}", lang, e))?;
+            bundles.push((lang, bundle));
+        }
+        Ok(Self { bundles })
+    }
+
+    fn format(&self, lang: &str, id: &str, args: Option<&FluentArgs>) -> Option<String> {
+        let (_, bundle) = self.bundles.iter().find(|(l, _)| *l == lang)?;
+        let pattern = bundle.get_message(id)?.value()?;
+        let mut errors = vec![];
+        let message = bundle.format_pattern(pattern, args, &mut errors);
+        if !errors.is_empty() {
+            eprintln!("Error formatting message {} in {}: {:?}", id, lang, errors);
+        }
+        Some(message.to_string())
+    }
+
+    /// Formats a message, falling back to the default language, and then to its ID.
+    pub fn message(&self, lang: &str, id: &str, args: Option<&FluentArgs>) -> String {
+        self.format(lang, id, args)
+            .or_else(|| self.format(DEFAULT_LANGUAGE, id, args))
+            .unwrap_or_else(|| id.to_string())
+    }
+
+    pub fn html(&self, lang: &str) -> liquid::Object {
+        let mut messages = liquid::Object::new();
+        for id in HTML_MESSAGES {
+            let message = self.message(lang, format!("html-{}", id).as_str(), None);
+            messages.insert(
+                id.replace('-', "_").into(),
+                liquid::model::Value::scalar(message),
+            );
+        }
+        messages
+    }
+}
+
+/// Supported languages, as tag and name (in that same language), for the HTML templates.
+pub fn languages() -> Vec<liquid::Object> {
+    CATALOGS
+        .iter()
+        .map(|(lang, _)| {
+            liquid::object!({
+                "tag": lang,
+                "name": I18N.message(lang, "language-name", None),
+            })
+        })
+        .collect()
+}
+
+/// Finds the supported language for a tag, either exactly or by its primary language (e.g. `pt`
+/// for `pt-BR`).
+pub fn supported(tag: &str) -> Option<&'static str> {
+    let requested: LanguageIdentifier = tag.trim().parse().ok()?;
+    let languages = CATALOGS.iter().map(|(lang, _)| *lang);
+    languages
+        .clone()
+        .find(|lang| lang.parse::<LanguageIdentifier>().ok().as_ref() == Some(&requested))
+        .or_else(|| {
+            languages.clone().find(|lang| {
+                lang.parse::<LanguageIdentifier>()
+                    .is_ok_and(|l| l.language == requested.language)
+            })
+        })
+}
+
+/// Picks the best supported language from an `Accept-Language` header.
+pub fn from_accept_language(header: &str) -> &'static str {
+    let mut options: Vec<(f32, &str)> = header
+        .split(',')
+        .filter_map(|option| {
+            let mut parts = option.split(';');
+            let tag = parts.next()?.trim();
+            let quality = parts
+                .find_map(|p| p.trim().strip_prefix("q="))
+                .map(|q| q.parse::<f32>().unwrap_or(0.0))
+                .unwrap_or(1.0);
+            Some((quality, tag))
+        })
+        .collect();
+    options.sort_by(|a, b| b.0.total_cmp(&a.0));
+    options
+        .into_iter()
+        .filter(|(quality, _)| *quality > 0.0)
+        .find_map(|(_, tag)| supported(tag))
+        .unwrap_or(DEFAULT_LANGUAGE)
+}
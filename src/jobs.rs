@@ -0,0 +1,146 @@
+use chrono::{DateTime, Duration, Utc};
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use crate::cache::JOB_TTL;
+
+const MAX_ERRORS: usize = 100;
+const RETRY_AFTER: Duration = Duration::minutes(5);
+
+lazy_static! {
+    static ref JOBS: Mutex<HashMap<String, Job>> = Mutex::new(HashMap::new());
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    Listing,
+    Resolving,
+    Done,
+    Failed,
+}
+
+/// Progress of the generation of a user's calendar, kept after it finishes for troubleshooting.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Job {
+    pub status: Status,
+    pub repos: usize,
+    pub resolved: usize,
+    #[serde(default)]
+    pub failed: usize,
+    pub errors: Vec<String>,
+    pub started: DateTime<Utc>,
+    pub finished: Option<DateTime<Utc>>,
+}
+
+impl Job {
+    pub fn new(status: Status) -> Self {
+        Self {
+            status,
+            repos: 0,
+            resolved: 0,
+            failed: 0,
+            errors: vec![],
+            started: Utc::now(),
+            finished: None,
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        matches!(self.status, Status::Listing | Status::Resolving)
+    }
+
+    /// Share of repos already looked at, successfully or not, from 0 to 100.
+    pub fn percent(&self) -> usize {
+        match self.status {
+            Status::Done => 100,
+            _ if self.repos == 0 => 0,
+            _ => ((self.resolved + self.failed) * 100 / self.repos).min(100),
+        }
+    }
+}
+
+/// Jobs of this process, forgetting the ones that finished longer ago than the cache keeps them.
+fn jobs() -> MutexGuard<'static, HashMap<String, Job>> {
+    let mut jobs = JOBS.lock().unwrap_or_else(PoisonError::into_inner);
+    let now = Utc::now();
+    jobs.retain(|_, job| job.finished.is_none_or(|finished| now - finished < JOB_TTL));
+    jobs
+}
+
+/// Latest job of a user in this process, running or finished.
+pub fn job(username: &str) -> Option<Job> {
+    jobs().get(username).cloned()
+}
+
+/// Handle updating the job of a user while their calendar is generated.
+#[derive(Clone, Debug)]
+pub struct Tracker {
+    username: String,
+}
+
+impl Tracker {
+    /// Starts a job, unless one is already running for this user, or failed recently.
+    pub fn start(username: &str) -> Option<Self> {
+        let mut jobs = jobs();
+        if let Some(job) = jobs.get(username) {
+            let failed_recently = job.status == Status::Failed
+                && job
+                    .finished
+                    .is_some_and(|finished| Utc::now() - finished < RETRY_AFTER);
+            if job.is_running() || failed_recently {
+                return None;
+            }
+        }
+        jobs.insert(username.to_string(), Job::new(Status::Listing));
+        Some(Self {
+            username: username.to_string(),
+        })
+    }
+
+    fn update(&self, change: impl FnOnce(&mut Job)) {
+        if let Some(job) = jobs().get_mut(&self.username) {
+            change(job);
+        }
+    }
+
+    pub fn listed(&self, repos: usize) {
+        self.update(|job| {
+            job.status = Status::Resolving;
+            job.repos = repos;
+        });
+    }
+
+    pub fn resolved(&self) {
+        self.update(|job| job.resolved += 1);
+    }
+
+    /// Records a repo that could not be read.
+    pub fn failed(&self, error: impl std::fmt::Display) {
+        self.update(|job| job.failed += 1);
+        self.error(error);
+    }
+
+    pub fn error(&self, error: impl std::fmt::Display) {
+        self.update(|job| {
+            if job.errors.len() < MAX_ERRORS {
+                job.errors.push(error.to_string());
+            }
+        });
+    }
+
+    /// Marks the job as finished, returning its final state.
+    pub fn finish(self, succeeded: bool) -> Option<Job> {
+        self.update(|job| {
+            job.status = if succeeded {
+                Status::Done
+            } else {
+                Status::Failed
+            };
+            job.finished = Some(Utc::now());
+        });
+        job(&self.username)
+    }
+}
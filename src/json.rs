@@ -0,0 +1,107 @@
+use anyhow::{anyhow, Result};
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+use crate::feed::Settings;
+use crate::models::FirstCommit;
+
+const DEFAULT_PER_PAGE: usize = 100;
+const MAX_PER_PAGE: usize = 500;
+
+/// Query string parameters for paginating the JSON API.
+#[derive(Debug, Default, Deserialize)]
+pub struct Pagination {
+    page: Option<String>,
+    per_page: Option<String>,
+}
+
+impl Pagination {
+    /// Validated page (starting at 1) and page size.
+    pub fn parse(&self) -> Result<(usize, usize)> {
+        let parse = |name: &str, value: Option<&String>, default: usize| -> Result<usize> {
+            match value.filter(|v| !v.trim().is_empty()) {
+                None => Ok(default),
+                Some(v) => v
+                    .trim()
+                    .parse::<usize>()
+                    .map_err(|e| anyhow!("Invalid value for {}, {}: {}", name, v, e)),
+            }
+        };
+        let page = parse("page", self.page.as_ref(), 1)?;
+        let per_page = parse("per_page", self.per_page.as_ref(), DEFAULT_PER_PAGE)?;
+        if page == 0 {
+            return Err(anyhow!("Invalid value for page: must be 1 or more"));
+        }
+        if per_page == 0 || per_page > MAX_PER_PAGE {
+            return Err(anyhow!(
+                "Invalid value for per_page: must be between 1 and {}",
+                MAX_PER_PAGE
+            ));
+        }
+        Ok((page, per_page))
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct Birthday {
+    owner: String,
+    name: String,
+    url: String,
+    born: NaiveDate,
+    next_anniversary: NaiveDate,
+    age: i32,
+    days: i64,
+    message: String,
+    sha: String,
+    commit_url: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Upcoming {
+    page: usize,
+    per_page: usize,
+    total: usize,
+    birthdays: Vec<Birthday>,
+}
+
+/// Next birthday of each repository kept by the settings, sorted by how soon it happens.
+pub fn upcoming_from(
+    commits: &[FirstCommit],
+    settings: &Settings,
+    page: usize,
+    per_page: usize,
+) -> Result<Upcoming> {
+    let mut birthdays = vec![];
+    for commit in commits.iter().filter(|commit| settings.keeps(commit)) {
+        let next_anniversary = commit.next_anniversary(settings.leap_day)?;
+        let age = commit.age(next_anniversary.year());
+        if !settings.keeps_age(age) {
+            continue;
+        }
+        birthdays.push(Birthday {
+            owner: commit.owner.clone(),
+            name: commit.name.clone(),
+            url: commit.url.clone(),
+            born: commit.date.date(),
+            next_anniversary,
+            age,
+            days: commit.days_to_next_anniversary(settings.leap_day)?,
+            message: commit.message.clone(),
+            sha: commit.sha.clone(),
+            commit_url: commit.commit_url.clone(),
+        });
+    }
+    birthdays.sort_by(|a, b| a.days.cmp(&b.days).then_with(|| a.name.cmp(&b.name)));
+    let total = birthdays.len();
+    let birthdays = birthdays
+        .into_iter()
+        .skip((page - 1).saturating_mul(per_page))
+        .take(per_page)
+        .collect();
+    Ok(Upcoming {
+        page,
+        per_page,
+        total,
+        birthdays,
+    })
+}
@@ -0,0 +1,180 @@
+language-name = Deutsch
+
+## Calendar
+
+calendar-name = GitHub-Repository-Jahrestage von { $username }
+event-title = 🎂 { $repo } ({ $age ->
+    [one] { $age } Jahr
+   *[other] { $age } Jahre
+} alt)
+event-day-count = 🎂 { $repo } ist { $count } Tage alt
+event-power-of-two = 🎂 { $repo } ist 2^{ $exponent } = { $count } Tage alt
+event-half-birthday = 🎂 { $repo } ist { $count ->
+    [0] ein halbes Jahr
+   *[other] { $count }½ Jahre
+} alt
+event-monthsary = 🎂 { $repo } ist { $count ->
+    [one] { $count } Monat
+   *[other] { $count } Monate
+} alt
+event-first-release = 🚀 Erstes Release von { $repo } ({ $tag }) vor { $count ->
+    [one] { $count } Jahr
+   *[other] { $count } Jahren
+}
+event-stable-release = 🚀 { $repo } { $tag } vor { $count ->
+    [one] { $count } Jahr
+   *[other] { $count } Jahren
+}
+event-star-milestone = ⭐ { $repo } hat vor { $count ->
+    [one] { $count } Jahr
+   *[other] { $count } Jahren
+} { $stars } Sterne erreicht
+event-first-issue = 🐣 Erstes Issue von { $repo } vor { $count ->
+    [one] { $count } Jahr
+   *[other] { $count } Jahren
+}: { $title }
+event-first-pull-request = 🐣 Erster Pull Request von { $repo } vor { $count ->
+    [one] { $count } Jahr
+   *[other] { $count } Jahren
+}: { $title }
+event-first-contribution = 🤝 Erster gemergter Beitrag zu { $repo } vor { $count ->
+    [one] { $count } Jahr
+   *[other] { $count } Jahren
+}: { $title }
+event-first-fork = 🍴 Erster Fork von { $repo } vor { $count ->
+    [one] { $count } Jahr
+   *[other] { $count } Jahren
+}: { $title }
+timeline-first-commit = Erster Commit
+timeline-first-release = Erstes Release
+timeline-stable-release = 1.0
+timeline-star-milestone = Sterne-Meilenstein
+timeline-first-issue = Erstes Issue
+timeline-first-pull-request = Erster Pull Request
+timeline-first-contribution = Erster gemergter Beitrag
+timeline-first-fork = Erster Fork
+badge-label = Alter
+badge-age = { $age ->
+    [0] unter einem Jahr
+    [one] { $age } Jahr
+   *[other] { $age } Jahre
+}
+ordinal = { $n }.
+first-commit = Erster Commit { $sha }
+first-commit-by = Erster Commit { $sha } von { $author }
+born-on = Geboren am { $date }
+countdown = { $days ->
+    [0] heute
+    [one] morgen
+   *[other] in { $days } Tagen
+}
+month-1 = Januar
+month-2 = Februar
+month-3 = März
+month-4 = April
+month-5 = Mai
+month-6 = Juni
+month-7 = Juli
+month-8 = August
+month-9 = September
+month-10 = Oktober
+month-11 = November
+month-12 = Dezember
+weekday-1 = Mo
+weekday-2 = Di
+weekday-3 = Mi
+weekday-4 = Do
+weekday-5 = Fr
+weekday-6 = Sa
+weekday-7 = So
+explain-fork = Forks werden nicht aufgenommen
+explain-private = Private Repositories werden nicht aufgenommen
+explain-empty = Das Repository ist leer
+explain-no-default-branch = Das Repository hat keinen Standard-Branch
+explain-failed = Der erste Commit konnte nicht gelesen werden: { $detail }
+explain-pending = Wir suchen noch nach dem ersten Commit
+explain-no-commits = Bei der letzten Erstellung des Kalenders wurde kein erster Commit gefunden
+explain-filtered = Durch die Option { $detail } ausgeschlossen
+explain-no-events = Kein Geburtstag im Kalenderzeitraum erfüllt die Altersoptionen
+
+## Web page
+
+html-title = Repo Birthday
+html-tagline = Erfahre, wann deine Repos Geburtstag feiern!
+html-login-notice = Melde dich mit GitHub an — wir lesen nur öffentliche Repos, brauchen aber deine Erlaubnis, um sie in deinem Namen abzufragen.
+html-login-button = Mit GitHub anmelden
+html-add-url = Füge jetzt einfach die <code>.ical</code>-URL zu deinem Lieblingskalender hinzu!
+html-private-only = Dieser Kalender ist nur über eine private URL verfügbar.
+html-private-url = Deine private URL
+html-copy-secret = Kopiere sie jetzt: Zu deiner Sicherheit speichern wir sie nicht und zeigen sie nicht noch einmal an.
+html-private-feeds = Gespeicherte Feeds sind auch darunter verfügbar, z. B.
+html-has-secret = Du hast bereits eine private URL. Wenn du eine neue erzeugst, funktioniert die alte nicht mehr.
+html-no-secret = Eine private URL kann nicht von Leuten erraten werden, die deinen Benutzernamen kennen, und du kannst sie jederzeit widerrufen.
+html-generate-secret = Private URL erzeugen
+html-regenerate-secret = Private URL neu erzeugen
+html-public = Meine Kalender auch über öffentliche URLs ohne Geheimnis bereitstellen
+html-language = Sprache
+html-preferred-language = Bevorzugte Sprache
+html-save = Speichern
+html-feeds = Deine Feeds
+html-feeds-intro = Speichere die gewünschten Optionen als benannten Feed und erhalte eine URL dafür. Ein Feed mit einem vorhandenen Namen wird ersetzt.
+html-delete = Löschen
+html-name = Name
+html-leap-day = Geburtstage am 29. Februar
+html-leap-mar1 = 1. März
+html-leap-feb28 = 28. Februar
+html-leap-only = Nur in Schaltjahren
+html-past = Jahre davor
+html-future = Jahre danach
+html-remind = Erinnerungen
+html-remind-milestones = Erinnerungen an Meilensteine
+html-milestones = Nur Meilensteine
+html-min-age = Mindestalter
+html-min-stars = Mindestanzahl Sterne
+html-min-commits = Mindestanzahl Commits
+html-include = Einschließen
+html-exclude = Ausschließen
+html-summary-template = Vorlage für den Termintitel
+html-description-template = Vorlage für die Terminbeschreibung
+html-save-feed = Feed speichern
+html-upcoming = Kommende Geburtstage
+html-loading = Wir suchen noch den ersten Commit jedes Repositorys. Diese Seite lädt sich selbst neu, bis alles bereit ist.
+html-search = Repositorys oder Commits suchen
+html-filter = Filtern
+html-repository = Repository
+html-born = Geboren
+html-age = Alter
+html-countdown = Nächster Geburtstag
+html-headline = Erster Commit
+html-no-repos = Keine Repositorys gefunden.
+html-calendar-view = Kalenderansicht
+html-previous = Zurück
+html-next = Weiter
+html-whole-year = Ganzes Jahr
+html-repositories = Repositorys
+html-errors = Fehler
+html-failed = Wir konnten die ersten Commits deiner Repositorys nicht finden. Wir versuchen es in ein paar Minuten erneut.
+html-failures = Einige Repositories konnten nicht gelesen werden und fehlen im Kalender
+html-include-failures = Nicht lesbare Repositories als X-Eigenschaften aufnehmen
+html-explain = Warum fehlt mein Repository?
+html-explain-intro = Alle Repositories, die GitHub für dich liefert, und warum sie im Kalender sind oder nicht. Füge die Kalenderoptionen zur URL hinzu, oder ?feed= mit dem Namen eines gespeicherten Feeds, um auch diese zu prüfen.
+html-decision = Entscheidung
+html-included = Aufgenommen, datiert nach
+html-occasions = Auch feiern
+html-day-counts = Alle 1000 Tage
+html-binary-birthdays = Binäre Geburtstage (256, 512, 1024… Tage)
+html-half-birthdays = Halbe Geburtstage
+html-monthsaries = Monatstage im ersten Jahr
+html-first-release = Jahrestage des ersten Releases
+html-stable-release = Jahrestage von 1.0
+html-star-milestones = Jahrestage von Sterne-Meilensteinen
+html-timeline = Zeitleiste
+html-firsts = Jahrestage des ersten Issues, Pull Requests, Beitrags und Forks
+html-organizations = Organisationen
+html-organizations-intro = Mitglieder einer Organisation können sie registrieren, um einen Kalender ihrer Repositories zu erhalten. Melde dich zuerst erneut an und gewähre Zugriff auf deine Organisationen; private Repositories werden nur aufgeführt, wenn du auch auf sie Zugriff gewährst, und nur in der privaten URL der Organisation.
+html-org-login-button = Zugriff auf Organisationen gewähren
+html-register-org = Organisation registrieren
+html-org-public-url = Öffentliche URL, nur mit öffentlichen Repositories
+html-org-private-url = Private URL, einschließlich privater Repositories
+html-org-public-only = Private Repositories fehlen: Melde dich erneut an, gewähre Zugriff auf sie und registriere die Organisation noch einmal, um sie aufzunehmen.
+html-open-source = Dieses Projekt ist Open Source
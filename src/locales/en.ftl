@@ -0,0 +1,186 @@
+language-name = English
+
+## Calendar
+
+calendar-name = { $username }'s GitHub repository anniversaries
+event-title = 🎂 { $repo } ({ $age ->
+    [one] { $age } year
+   *[other] { $age } years
+} old)
+event-day-count = 🎂 { $repo } is { $count } days old
+event-power-of-two = 🎂 { $repo } is 2^{ $exponent } = { $count } days old
+event-half-birthday = 🎂 { $repo } is { $count ->
+    [0] half a year
+    [one] { $count }½ years
+   *[other] { $count }½ years
+} old
+event-monthsary = 🎂 { $repo } is { $count ->
+    [one] { $count } month
+   *[other] { $count } months
+} old
+event-first-release = 🚀 { $repo } first release ({ $tag }) { $count ->
+    [one] { $count } year
+   *[other] { $count } years
+} ago
+event-stable-release = 🚀 { $repo } { $tag } { $count ->
+    [one] { $count } year
+   *[other] { $count } years
+} ago
+event-star-milestone = ⭐ { $repo } reached { $stars } stars { $count ->
+    [one] { $count } year
+   *[other] { $count } years
+} ago
+event-first-issue = 🐣 { $repo } first issue { $count ->
+    [one] { $count } year
+   *[other] { $count } years
+} ago: { $title }
+event-first-pull-request = 🐣 { $repo } first pull request { $count ->
+    [one] { $count } year
+   *[other] { $count } years
+} ago: { $title }
+event-first-contribution = 🤝 { $repo } first merged contribution { $count ->
+    [one] { $count } year
+   *[other] { $count } years
+} ago: { $title }
+event-first-fork = 🍴 { $repo } first fork { $count ->
+    [one] { $count } year
+   *[other] { $count } years
+} ago: { $title }
+timeline-first-commit = First commit
+timeline-first-release = First release
+timeline-stable-release = 1.0
+timeline-star-milestone = Star milestone
+timeline-first-issue = First issue
+timeline-first-pull-request = First pull request
+timeline-first-contribution = First merged contribution
+timeline-first-fork = First fork
+badge-label = age
+badge-age = { $age ->
+    [0] less than a year
+    [one] { $age } year
+   *[other] { $age } years
+}
+ordinal = { NUMBER($n, type: "ordinal") ->
+    [one] { $n }st
+    [two] { $n }nd
+    [few] { $n }rd
+   *[other] { $n }th
+}
+first-commit = First commit { $sha }
+first-commit-by = First commit { $sha } by { $author }
+born-on = Born on { $date }
+countdown = { $days ->
+    [0] today
+    [one] tomorrow
+   *[other] in { $days } days
+}
+month-1 = January
+month-2 = February
+month-3 = March
+month-4 = April
+month-5 = May
+month-6 = June
+month-7 = July
+month-8 = August
+month-9 = September
+month-10 = October
+month-11 = November
+month-12 = December
+weekday-1 = Mon
+weekday-2 = Tue
+weekday-3 = Wed
+weekday-4 = Thu
+weekday-5 = Fri
+weekday-6 = Sat
+weekday-7 = Sun
+explain-fork = Forks are not included
+explain-private = Private repositories are not included
+explain-empty = The repository is empty
+explain-no-default-branch = The repository has no default branch
+explain-failed = Its first commit could not be read: { $detail }
+explain-pending = We are still looking for its first commit
+explain-no-commits = No first commit was found when the calendar was last generated
+explain-filtered = Left out by the option { $detail }
+explain-no-events = No birthday in the calendar window passes the age options
+
+## Web page
+
+html-title = Repo Birthday
+html-tagline = Know when your repos are celebrating their birthdays!
+html-login-notice = Login with GitHub — we only read public repos but we need your permission to query on our behalf.
+html-login-button = Login with GitHub
+html-add-url = Now just add the <code>.ical</code> URL to your favorite calendar!
+html-private-only = This calendar is only available through a private URL.
+html-private-url = Your private URL
+html-copy-secret = Copy it now: for your security, we do not store it and will not show it again.
+html-private-feeds = Saved feeds are available under it too, e.g.
+html-has-secret = You already have a private URL. Generating a new one stops the old one from working.
+html-no-secret = A private URL cannot be guessed by people who know your username, and you can revoke it anytime.
+html-generate-secret = Generate private URL
+html-regenerate-secret = Regenerate private URL
+html-public = Also serve my calendars from public URLs, without the secret
+html-language = Language
+html-preferred-language = Preferred language
+html-save = Save
+html-feeds = Your feeds
+html-feeds-intro = Save the options you like as a named feed, and get a URL for it. Saving a feed with an existing name replaces it.
+html-delete = Delete
+html-name = Name
+html-leap-day = Leap day birthdays
+html-leap-mar1 = March 1st
+html-leap-feb28 = February 28th
+html-leap-only = Only on leap years
+html-past = Years before
+html-future = Years after
+html-remind = Reminders
+html-remind-milestones = Milestone reminders
+html-milestones = Milestones only
+html-min-age = Minimum age
+html-min-stars = Minimum stars
+html-min-commits = Minimum commits
+html-include = Include
+html-exclude = Exclude
+html-summary-template = Event title template
+html-description-template = Event description template
+html-save-feed = Save feed
+html-upcoming = Upcoming birthdays
+html-loading = We are still looking for the first commit of each repository. This page reloads by itself until they are ready.
+html-search = Search repositories or commits
+html-filter = Filter
+html-repository = Repository
+html-born = Born
+html-age = Age
+html-countdown = Next birthday
+html-headline = First commit
+html-no-repos = No repositories found.
+html-calendar-view = Calendar view
+html-previous = Previous
+html-next = Next
+html-whole-year = Whole year
+html-repositories = repositories
+html-errors = errors
+html-failed = We could not find the first commits of your repositories. We will try again in a few minutes.
+html-failures = Some repositories could not be read and are missing from the calendar
+html-include-failures = Include repositories that could not be read as X-properties
+html-explain = Why isn't my repo here?
+html-explain-intro = Every repository GitHub returns for you, and why it is or is not in the calendar. Add the calendar options to the URL, or ?feed= with the name of a saved feed, to check them too.
+html-decision = Decision
+html-included = Included, dated by
+html-occasions = Also celebrate
+html-day-counts = Every 1000 days
+html-binary-birthdays = Binary birthdays (256, 512, 1024… days)
+html-half-birthdays = Half-birthdays
+html-monthsaries = Monthsaries in the first year
+html-first-release = First release anniversaries
+html-stable-release = 1.0 anniversaries
+html-star-milestones = Star milestone anniversaries
+html-timeline = Timeline
+html-firsts = Anniversaries of the first issue, pull request, contribution and fork
+html-organizations = Organizations
+html-organizations-intro = Members of an organization can register it to get a calendar of its repositories. Log in again granting access to your organizations first; private repositories are only listed if you also grant access to them, and only in the organization's private URL.
+html-org-login-button = Grant access to organizations
+html-register-org = Register organization
+html-org-public-url = Public URL, with public repositories only
+html-org-private-url = Private URL, including private repositories
+html-org-public-only = Private repositories are left out: log in again granting access to them, and register the organization again, to include them.
+html-open-source = This project is open-source
@@ -0,0 +1,151 @@
+language-name = 日本語
+
+## Calendar
+
+calendar-name = { $username } の GitHub リポジトリ記念日
+event-title = 🎂 { $repo }（{ $age }歳）
+event-day-count = 🎂 { $repo } は { $count } 日目
+event-power-of-two = 🎂 { $repo } は 2^{ $exponent } = { $count } 日目
+event-half-birthday = 🎂 { $repo } は { $count ->
+    [0] 生後半年
+   *[other] { $count } 歳半
+}
+event-monthsary = 🎂 { $repo } は生後 { $count } か月
+event-first-release = 🚀 { $repo } の最初のリリース（{ $tag }）から { $count } 年
+event-stable-release = 🚀 { $repo } { $tag } から { $count } 年
+event-star-milestone = ⭐ { $repo } がスター { $stars } 個に到達してから { $count } 年
+event-first-issue = 🐣 { $repo } の最初の issue から { $count } 年: { $title }
+event-first-pull-request = 🐣 { $repo } の最初のプルリクエストから { $count } 年: { $title }
+event-first-contribution = 🤝 { $repo } に最初の外部コントリビューションがマージされてから { $count } 年: { $title }
+event-first-fork = 🍴 { $repo } の最初のフォークから { $count } 年: { $title }
+timeline-first-commit = 最初のコミット
+timeline-first-release = 最初のリリース
+timeline-stable-release = 1.0
+timeline-star-milestone = スター数の節目
+timeline-first-issue = 最初の issue
+timeline-first-pull-request = 最初のプルリクエスト
+timeline-first-contribution = 最初にマージされた外部コントリビューション
+timeline-first-fork = 最初のフォーク
+badge-label = 年齢
+badge-age = { $age ->
+    [0] 1 年未満
+   *[other] { $age } 年
+}
+ordinal = { $n }回目
+first-commit = 最初のコミット { $sha }
+first-commit-by = 最初のコミット { $sha }（{ $author }）
+born-on = 誕生日 { $date }
+countdown = { $days ->
+    [0] 今日
+   *[other] { $days }日後
+}
+month-1 = 1月
+month-2 = 2月
+month-3 = 3月
+month-4 = 4月
+month-5 = 5月
+month-6 = 6月
+month-7 = 7月
+month-8 = 8月
+month-9 = 9月
+month-10 = 10月
+month-11 = 11月
+month-12 = 12月
+weekday-1 = 月
+weekday-2 = 火
+weekday-3 = 水
+weekday-4 = 木
+weekday-5 = 金
+weekday-6 = 土
+weekday-7 = 日
+explain-fork = フォークは含まれません
+explain-private = プライベートリポジトリは含まれません
+explain-empty = リポジトリが空です
+explain-no-default-branch = リポジトリにデフォルトブランチがありません
+explain-failed = 最初のコミットを読み込めませんでした: { $detail }
+explain-pending = 最初のコミットをまだ探しています
+explain-no-commits = 前回カレンダーを生成したときに最初のコミットが見つかりませんでした
+explain-filtered = オプション { $detail } により除外されました
+explain-no-events = カレンダーの期間内に年齢のオプションを満たす誕生日がありません
+
+## Web page
+
+html-title = Repo Birthday
+html-tagline = リポジトリの誕生日を知ろう！
+html-login-notice = GitHub でログインしてください。公開リポジトリしか読みませんが、あなたの代わりに問い合わせるための許可が必要です。
+html-login-button = GitHub でログイン
+html-add-url = あとは <code>.ical</code> の URL をお気に入りのカレンダーに追加するだけ！
+html-private-only = このカレンダーはプライベート URL でのみ利用できます。
+html-private-url = あなたのプライベート URL
+html-copy-secret = 今すぐコピーしてください。安全のため保存しておらず、再表示はできません。
+html-private-feeds = 保存したフィードもこの下で利用できます。例：
+html-has-secret = プライベート URL はすでにあります。新しく生成すると古い URL は使えなくなります。
+html-no-secret = プライベート URL はユーザー名を知っている人にも推測できず、いつでも無効にできます。
+html-generate-secret = プライベート URL を生成
+html-regenerate-secret = プライベート URL を再生成
+html-public = シークレットなしの公開 URL でもカレンダーを提供する
+html-language = 言語
+html-preferred-language = 優先言語
+html-save = 保存
+html-feeds = あなたのフィード
+html-feeds-intro = 好きなオプションを名前付きフィードとして保存すると、専用の URL が手に入ります。同じ名前で保存すると置き換えられます。
+html-delete = 削除
+html-name = 名前
+html-leap-day = 2 月 29 日生まれの誕生日
+html-leap-mar1 = 3 月 1 日
+html-leap-feb28 = 2 月 28 日
+html-leap-only = うるう年のみ
+html-past = 過去の年数
+html-future = 未来の年数
+html-remind = リマインダー
+html-remind-milestones = 節目のリマインダー
+html-milestones = 節目のみ
+html-min-age = 最低年齢
+html-min-stars = 最低スター数
+html-min-commits = 最低コミット数
+html-include = 含める
+html-exclude = 除外する
+html-summary-template = イベントタイトルのテンプレート
+html-description-template = イベント説明のテンプレート
+html-save-feed = フィードを保存
+html-upcoming = 今後の誕生日
+html-loading = 各リポジトリの最初のコミットを探しています。準備ができるまで、このページは自動的に再読み込みされます。
+html-search = リポジトリやコミットを検索
+html-filter = 絞り込む
+html-repository = リポジトリ
+html-born = 誕生日
+html-age = 年齢
+html-countdown = 次の誕生日
+html-headline = 最初のコミット
+html-no-repos = リポジトリが見つかりません。
+html-calendar-view = カレンダー表示
+html-previous = 前へ
+html-next = 次へ
+html-whole-year = 年全体
+html-repositories = リポジトリ
+html-errors = エラー
+html-failed = リポジトリの最初のコミットが見つかりませんでした。数分後にもう一度試します。
+html-failures = 一部のリポジトリを読み込めなかったため、カレンダーに含まれていません
+html-include-failures = 読み込めなかったリポジトリを X プロパティとして含める
+html-explain = リポジトリが表示されないのはなぜ？
+html-explain-intro = GitHub が返すすべてのリポジトリと、それがカレンダーに含まれるかどうかの理由です。カレンダーのオプションを URL に追加するか、?feed= に保存したフィードの名前を指定して確認することもできます。
+html-decision = 判定
+html-included = 含まれています。日付の根拠:
+html-occasions = ほかのお祝い
+html-day-counts = 1000 日ごと
+html-binary-birthdays = 2 進数の誕生日（256、512、1024… 日）
+html-half-birthdays = ハーフバースデー
+html-monthsaries = 最初の 1 年の月誕生日
+html-first-release = 最初のリリースの記念日
+html-stable-release = 1.0 の記念日
+html-star-milestones = スター数の節目の記念日
+html-timeline = タイムライン
+html-firsts = 最初の issue、プルリクエスト、コントリビューション、フォークの記念日
+html-organizations = Organization
+html-organizations-intro = Organization のメンバーは、それを登録してリポジトリのカレンダーを取得できます。まず Organization へのアクセスを許可して再ログインしてください。プライベートリポジトリは、そのアクセスも許可した場合にのみ、Organization のプライベート URL にだけ含まれます。
+html-org-login-button = Organization へのアクセスを許可
+html-register-org = Organization を登録
+html-org-public-url = 公開 URL(公開リポジトリのみ)
+html-org-private-url = プライベート URL(プライベートリポジトリを含む)
+html-org-public-only = プライベートリポジトリは含まれていません。含めるには、アクセスを許可して再ログインし、Organization をもう一度登録してください。
+html-open-source = このプロジェクトはオープンソースです
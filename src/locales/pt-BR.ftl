@@ -0,0 +1,180 @@
+language-name = Português (Brasil)
+
+## Calendar
+
+calendar-name = Aniversários dos repositórios de { $username } no GitHub
+event-title = 🎂 { $repo } ({ $age ->
+    [one] { $age } ano
+   *[other] { $age } anos
+})
+event-day-count = 🎂 { $repo } completa { $count } dias
+event-power-of-two = 🎂 { $repo } completa 2^{ $exponent } = { $count } dias
+event-half-birthday = 🎂 { $repo } completa { $count ->
+    [0] meio ano
+   *[other] { $count } anos e meio
+}
+event-monthsary = 🎂 { $repo } completa { $count ->
+    [one] { $count } mês
+   *[other] { $count } meses
+}
+event-first-release = 🚀 Primeira versão de { $repo } ({ $tag }) há { $count ->
+    [one] { $count } ano
+   *[other] { $count } anos
+}
+event-stable-release = 🚀 { $repo } { $tag } há { $count ->
+    [one] { $count } ano
+   *[other] { $count } anos
+}
+event-star-milestone = ⭐ { $repo } chegou a { $stars } estrelas há { $count ->
+    [one] { $count } ano
+   *[other] { $count } anos
+}
+event-first-issue = 🐣 Primeira issue de { $repo } há { $count ->
+    [one] { $count } ano
+   *[other] { $count } anos
+}: { $title }
+event-first-pull-request = 🐣 Primeiro pull request de { $repo } há { $count ->
+    [one] { $count } ano
+   *[other] { $count } anos
+}: { $title }
+event-first-contribution = 🤝 Primeira contribuição aceita em { $repo } há { $count ->
+    [one] { $count } ano
+   *[other] { $count } anos
+}: { $title }
+event-first-fork = 🍴 Primeiro fork de { $repo } há { $count ->
+    [one] { $count } ano
+   *[other] { $count } anos
+}: { $title }
+timeline-first-commit = Primeiro commit
+timeline-first-release = Primeira versão
+timeline-stable-release = 1.0
+timeline-star-milestone = Marco de estrelas
+timeline-first-issue = Primeira issue
+timeline-first-pull-request = Primeiro pull request
+timeline-first-contribution = Primeira contribuição aceita
+timeline-first-fork = Primeiro fork
+badge-label = idade
+badge-age = { $age ->
+    [0] menos de um ano
+    [one] { $age } ano
+   *[other] { $age } anos
+}
+ordinal = { $n }º
+first-commit = Primeiro commit { $sha }
+first-commit-by = Primeiro commit { $sha } por { $author }
+born-on = Nasceu em { $date }
+countdown = { $days ->
+    [0] hoje
+    [one] amanhã
+   *[other] em { $days } dias
+}
+month-1 = Janeiro
+month-2 = Fevereiro
+month-3 = Março
+month-4 = Abril
+month-5 = Maio
+month-6 = Junho
+month-7 = Julho
+month-8 = Agosto
+month-9 = Setembro
+month-10 = Outubro
+month-11 = Novembro
+month-12 = Dezembro
+weekday-1 = Seg
+weekday-2 = Ter
+weekday-3 = Qua
+weekday-4 = Qui
+weekday-5 = Sex
+weekday-6 = Sáb
+weekday-7 = Dom
+explain-fork = Forks não são incluídos
+explain-private = Repositórios privados não são incluídos
+explain-empty = O repositório está vazio
+explain-no-default-branch = O repositório não tem branch padrão
+explain-failed = Não foi possível ler o primeiro commit: { $detail }
+explain-pending = Ainda estamos procurando o primeiro commit
+explain-no-commits = Nenhum primeiro commit foi encontrado na última vez que o calendário foi gerado
+explain-filtered = Removido pela opção { $detail }
+explain-no-events = Nenhum aniversário no período do calendário passa pelas opções de idade
+
+## Web page
+
+html-title = Repo Birthday
+html-tagline = Saiba quando seus repositórios fazem aniversário!
+html-login-notice = Entre com o GitHub — só lemos repositórios públicos, mas precisamos da sua permissão para consultá-los em seu nome.
+html-login-button = Entrar com o GitHub
+html-add-url = Agora é só adicionar a URL <code>.ical</code> ao seu calendário favorito!
+html-private-only = Este calendário só está disponível por uma URL privada.
+html-private-url = Sua URL privada
+html-copy-secret = Copie agora: para sua segurança, não a guardamos e não vamos mostrá-la novamente.
+html-private-feeds = Os feeds salvos também ficam disponíveis nela, por exemplo
+html-has-secret = Você já tem uma URL privada. Gerar uma nova faz a antiga parar de funcionar.
+html-no-secret = Uma URL privada não pode ser adivinhada por quem sabe seu usuário, e você pode revogá-la quando quiser.
+html-generate-secret = Gerar URL privada
+html-regenerate-secret = Gerar nova URL privada
+html-public = Servir meus calendários também em URLs públicas, sem o segredo
+html-language = Idioma
+html-preferred-language = Idioma preferido
+html-save = Salvar
+html-feeds = Seus feeds
+html-feeds-intro = Salve as opções que quiser como um feed com nome e ganhe uma URL para ele. Salvar um feed com um nome existente o substitui.
+html-delete = Apagar
+html-name = Nome
+html-leap-day = Aniversários em 29 de fevereiro
+html-leap-mar1 = 1º de março
+html-leap-feb28 = 28 de fevereiro
+html-leap-only = Só em anos bissextos
+html-past = Anos antes
+html-future = Anos depois
+html-remind = Lembretes
+html-remind-milestones = Lembretes de marcos
+html-milestones = Só marcos
+html-min-age = Idade mínima
+html-min-stars = Mínimo de estrelas
+html-min-commits = Mínimo de commits
+html-include = Incluir
+html-exclude = Excluir
+html-summary-template = Template do título do evento
+html-description-template = Template da descrição do evento
+html-save-feed = Salvar feed
+html-upcoming = Próximos aniversários
+html-loading = Ainda estamos procurando o primeiro commit de cada repositório. Esta página recarrega sozinha até que estejam prontos.
+html-search = Buscar repositórios ou commits
+html-filter = Filtrar
+html-repository = Repositório
+html-born = Nascimento
+html-age = Idade
+html-countdown = Próximo aniversário
+html-headline = Primeiro commit
+html-no-repos = Nenhum repositório encontrado.
+html-calendar-view = Ver calendário
+html-previous = Anterior
+html-next = Próximo
+html-whole-year = Ano inteiro
+html-repositories = repositórios
+html-errors = erros
+html-failed = Não conseguimos encontrar o primeiro commit dos seus repositórios. Tentaremos novamente em alguns minutos.
+html-failures = Alguns repositórios não puderam ser lidos e estão fora do calendário
+html-include-failures = Incluir repositórios que não puderam ser lidos como propriedades X
+html-explain = Por que meu repositório não está aqui?
+html-explain-intro = Todos os repositórios que o GitHub retorna para você, e por que estão ou não no calendário. Adicione as opções do calendário à URL, ou ?feed= com o nome de um feed salvo, para verificá-las também.
+html-decision = Decisão
+html-included = Incluído, datado por
+html-occasions = Também comemorar
+html-day-counts = A cada 1000 dias
+html-binary-birthdays = Aniversários binários (256, 512, 1024… dias)
+html-half-birthdays = Meio-aniversários
+html-monthsaries = Mesversários no primeiro ano
+html-first-release = Aniversários da primeira versão
+html-stable-release = Aniversários da versão 1.0
+html-star-milestones = Aniversários de marcos de estrelas
+html-timeline = Linha do tempo
+html-firsts = Aniversários da primeira issue, pull request, contribuição e fork
+html-organizations = Organizações
+html-organizations-intro = Membros de uma organização podem registrá-la para ter um calendário dos seus repositórios. Primeiro entre novamente dando acesso às suas organizações; repositórios privados só são listados se você também der acesso a eles, e apenas na URL privada da organização.
+html-org-login-button = Dar acesso às organizações
+html-register-org = Registrar organização
+html-org-public-url = URL pública, só com repositórios públicos
+html-org-private-url = URL privada, incluindo repositórios privados
+html-org-public-only = Repositórios privados ficaram de fora: entre novamente dando acesso a eles, e registre a organização de novo, para incluí-los.
+html-open-source = Este projeto é open-source
@@ -1,12 +1,26 @@
+mod atom;
 mod auth;
+mod badge;
 mod cache;
 mod calendar;
 mod commits;
+mod dashboard;
 mod date_time_serializer;
 mod envvar;
+mod explain;
+mod feed;
+mod filters;
+mod firsts;
+mod formats;
 mod graphql;
+mod grid;
+mod i18n;
+mod jobs;
+mod json;
 mod models;
+mod registry;
 mod repositories;
+mod stargazers;
 mod templates;
 mod web;
 
@@ -31,6 +45,32 @@ async fn main() -> std::io::Result<()> {
             .service(web::callback)
             .service(web::calendar)
             .service(web::calendar_alt)
+            .service(web::private_calendar)
+            .service(web::json)
+            .service(web::private_json)
+            .service(web::atom)
+            .service(web::private_atom)
+            .service(web::registry)
+            .service(web::private_registry)
+            .service(web::feed_calendar)
+            .service(web::private_feed_calendar)
+            .service(web::regenerate_secret)
+            .service(web::save_preferences)
+            .service(web::save_feed)
+            .service(web::delete_feed)
+            .service(web::job_status)
+            .service(web::progress)
+            .service(web::org_calendar)
+            .service(web::private_org_calendar)
+            .service(web::register_org)
+            .service(web::repo_calendar)
+            .service(web::repo_badge)
+            .service(web::this_year_calendar)
+            .service(web::year_calendar)
+            .service(web::month_calendar)
+            .service(web::timeline_page)
+            .service(web::explain_page)
+            .service(web::explain_json)
             .service(web::view)
     })
     .bind((web::DEFAULT_IP, port))?
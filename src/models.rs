@@ -1,27 +1,186 @@
+use anyhow::{anyhow, Result};
 use chrono::prelude::*;
-use chrono::{NaiveDate, NaiveDateTime};
+use chrono::{Months, NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
 
+// leap-day repos celebrating only on leap years might wait 8 years (e.g. 2096 to 2104)
+const MAX_YEARS_BETWEEN_ANNIVERSARIES: i32 = 8;
+const MAX_FAILURE_LENGTH: usize = 300;
+const DAY_COUNT_STEP: i64 = 1000;
+// binary birthdays start at 256 days, younger repos already have monthsaries
+const MIN_POWER_OF_TWO: u32 = 8;
+const MONTHS_IN_A_YEAR: u32 = 12;
+
+/// Where repos born on February 29th celebrate their birthdays in non-leap years.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub enum LeapDay {
+    February28,
+    #[default]
+    March1,
+    LeapYearsOnly,
+}
+
+/// Celebrations other than the yearly anniversary, each one opted into by feeds.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Occasion {
+    /// Every 1000 days (1000, 2000… 10000…).
+    DayCount,
+    /// Days that are powers of two (256, 512, 1024…).
+    PowerOfTwo,
+    /// Six months after each birthday.
+    HalfBirthday,
+    /// Every month of the first year.
+    Monthsary,
+    /// Anniversaries of the first published release.
+    FirstRelease,
+    /// Anniversaries of the oldest 1.0 tag or release.
+    StableRelease,
+    /// Anniversaries of reaching round numbers of stars.
+    StarMilestone,
+    /// Anniversaries of the community firsts.
+    FirstIssue,
+    FirstPullRequest,
+    FirstContribution,
+    FirstFork,
+}
+
+impl Occasion {
+    /// Whether it celebrates one of the community firsts.
+    pub fn is_first(&self) -> bool {
+        matches!(
+            self,
+            Self::FirstIssue | Self::FirstPullRequest | Self::FirstContribution | Self::FirstFork
+        )
+    }
+}
+
+/// A release a repo celebrates the anniversaries of, besides its first commit.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Release {
+    pub tag: String,
+    pub date: NaiveDateTime,
+    pub url: String,
+}
+
+/// Something that happened for the first time in a repo, besides its first commit.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct First {
+    pub title: String,
+    pub url: String,
+    pub date: NaiveDateTime,
+    pub author: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct Firsts {
+    pub issue: Option<First>,
+    pub pull_request: Option<First>,
+    /// The first pull request by an outside contributor to be merged.
+    pub contribution: Option<First>,
+    pub fork: Option<First>,
+}
+
+impl Firsts {
+    /// Once all of them happened, they never change.
+    pub fn is_complete(&self) -> bool {
+        self.issue.is_some()
+            && self.pull_request.is_some()
+            && self.contribution.is_some()
+            && self.fork.is_some()
+    }
+}
+
+/// When a repo reached a round number of stars.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct StarMilestone {
+    pub stars: u32,
+    pub date: NaiveDateTime,
+}
+
+/// A non-annual celebration, and what it counts: days for day counts and powers of two, months
+/// for monthsaries, and years for the others.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Celebration {
+    pub occasion: Occasion,
+    pub date: NaiveDate,
+    pub count: i64,
+    pub stars: Option<u32>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct FirstCommit {
     pub message: String,
     pub date: NaiveDateTime,
     pub name: String,
     pub owner: String,
+    pub sha: String,
+    pub commit_url: String,
+    pub author: Option<String>,
+    pub url: String,
+    pub description: Option<String>,
+    pub language: Option<String>,
+    pub topics: Vec<String>,
+    pub stars: u32,
+    pub commits: u32,
+    #[serde(default)]
+    pub first_release: Option<Release>,
+    #[serde(default)]
+    pub stable_release: Option<Release>,
+    #[serde(default)]
+    pub star_milestones: Vec<StarMilestone>,
+    #[serde(default)]
+    pub firsts: Firsts,
+}
+
+/// A repository whose first commit could not be found, left out of the calendars.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Failure {
+    pub repo: String,
+    pub error: String,
+}
+
+impl Failure {
+    /// Keeps only the first line of the error, since some include whole API responses.
+    pub fn new(repo: String, error: impl std::fmt::Display) -> Self {
+        let error = error.to_string();
+        Self {
+            repo,
+            error: error
+                .lines()
+                .next()
+                .unwrap_or("")
+                .chars()
+                .take(MAX_FAILURE_LENGTH)
+                .collect(),
+        }
+    }
+}
+
+/// An organization registered by one of its members, whose token is used to list its repos.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct OrgRegistration {
+    pub member: String,
+    /// Whether the member granted access to private repos, which only secret feeds include.
+    pub private: bool,
+}
+
+impl std::fmt::Display for Failure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.repo, self.error)
+    }
 }
 
 impl std::fmt::Display for FirstCommit {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{} {}",
-            self.date,
-            self.message.lines().next().unwrap_or(""),
-        )
+        write!(f, "{} {}", self.date, self.headline())
     }
 }
 
-fn change_year(original: NaiveDate, year: i32) -> NaiveDate {
+fn change_year(original: NaiveDate, year: i32, policy: LeapDay) -> Result<Option<NaiveDate>> {
     if original.year() == year {
-        return original;
+        return Ok(Some(original));
     }
 
     let mut month = original.month();
@@ -29,38 +188,199 @@ fn change_year(original: NaiveDate, year: i32) -> NaiveDate {
     if month == 2 && day == 29 {
         let is_leap = (year % 4 == 0) && (year % 100 != 0 || year % 400 == 0);
         if !is_leap {
-            month = 3;
-            day = 1;
+            match policy {
+                LeapDay::February28 => day = 28,
+                LeapDay::March1 => {
+                    month = 3;
+                    day = 1;
+                }
+                LeapDay::LeapYearsOnly => return Ok(None),
+            }
         }
     }
 
-    match NaiveDate::from_ymd_opt(year, month, day) {
-        Some(date) => date,
-        None => panic!("Error moving date {original} to {year}"),
-    }
+    NaiveDate::from_ymd_opt(year, month, day)
+        .map(Some)
+        .ok_or_else(|| anyhow!("Error moving date {original} to {year}"))
+}
+
+/// Anniversaries of a date between two dates, both included, with how many years they celebrate.
+fn yearly(
+    original: NaiveDate,
+    from: NaiveDate,
+    to: NaiveDate,
+    policy: LeapDay,
+) -> Vec<(NaiveDate, i64)> {
+    (from.year().max(original.year() + 1)..=to.year())
+        .filter_map(|year| {
+            change_year(original, year, policy)
+                .ok()
+                .flatten()
+                .map(|date| (date, i64::from(year - original.year())))
+        })
+        .filter(|(date, _)| *date >= from && *date <= to)
+        .collect()
 }
 
 impl FirstCommit {
-    pub fn next_anniversary(&self) -> NaiveDate {
+    pub fn headline(&self) -> &str {
+        self.message.lines().next().unwrap_or("")
+    }
+
+    pub fn body(&self) -> &str {
+        match self.message.split_once('\n') {
+            Some((_, body)) => body.trim(),
+            None => "",
+        }
+    }
+
+    pub fn short_sha(&self) -> &str {
+        self.sha.get(..7).unwrap_or(self.sha.as_str())
+    }
+
+    /// Where the birth date comes from: the date of the oldest commit in the default branch.
+    pub fn date_source(&self) -> &'static str {
+        "first_commit"
+    }
+
+    /// The date this repo celebrates its birthday in a given year, if it does so in that year.
+    pub fn anniversary_in(&self, year: i32, policy: LeapDay) -> Result<Option<NaiveDate>> {
+        change_year(self.date.date(), year, policy)
+    }
+
+    pub fn next_anniversary(&self, policy: LeapDay) -> Result<NaiveDate> {
         let today = Local::now().date_naive();
-        let date = self.date.date();
-        if today == date {
-            return today;
+        if today == self.date.date() {
+            return Ok(today);
         }
 
-        let mut result = change_year(date, today.year());
-        if result < today {
-            result = change_year(date, today.year() + 1);
+        for year in today.year()..=today.year() + MAX_YEARS_BETWEEN_ANNIVERSARIES {
+            if let Some(date) = self.anniversary_in(year, policy)? {
+                if date >= today {
+                    return Ok(date);
+                }
+            }
         }
 
-        result
+        Err(anyhow!("No anniversary found for {} after {}", self, today))
     }
 
-    pub fn days_to_next_anniversary(&self) -> i64 {
-        (self.next_anniversary() - Local::now().date_naive()).num_days()
+    pub fn days_to_next_anniversary(&self, policy: LeapDay) -> Result<i64> {
+        Ok((self.next_anniversary(policy)? - Local::now().date_naive()).num_days())
     }
 
-    pub fn age(&self) -> i32 {
-        self.next_anniversary().year() - self.date.year()
+    pub fn age(&self, year: i32) -> i32 {
+        year - self.date.year()
+    }
+
+    /// Completed years on a date, counting the birthday when the leap day policy celebrates it.
+    pub fn age_on(&self, date: NaiveDate, policy: LeapDay) -> Result<i32> {
+        let celebrated = match self.anniversary_in(date.year(), policy)? {
+            Some(anniversary) => anniversary <= date,
+            None => (date.month(), date.day()) > (self.date.month(), self.date.day()),
+        };
+        let age = self.age(date.year());
+        Ok(if celebrated { age } else { age - 1 })
+    }
+
+    /// Celebrations of an occasion between two dates, both included. Yearly ones follow the leap
+    /// day policy like birthdays do.
+    pub fn celebrations_between(
+        &self,
+        occasion: Occasion,
+        from: NaiveDate,
+        to: NaiveDate,
+        policy: LeapDay,
+    ) -> Vec<Celebration> {
+        let born = self.date.date();
+        let celebration = |date: NaiveDate, count: i64| Celebration {
+            occasion,
+            date,
+            count,
+            stars: None,
+        };
+        let after_days = |days: i64| born.checked_add_signed(chrono::Duration::days(days));
+        let after_months = |months: u32| born.checked_add_months(Months::new(months));
+        let in_range = |date: &NaiveDate| *date >= from && *date <= to;
+        match occasion {
+            Occasion::DayCount => {
+                let first = ((from - born).num_days() / DAY_COUNT_STEP).max(1);
+                (first..)
+                    .map(|n| n * DAY_COUNT_STEP)
+                    .map_while(|days| after_days(days).map(|date| celebration(date, days)))
+                    .skip_while(|c| c.date < from)
+                    .take_while(|c| c.date <= to)
+                    .collect()
+            }
+            Occasion::PowerOfTwo => (MIN_POWER_OF_TWO..i64::BITS - 1)
+                .map(|exponent| 1_i64 << exponent)
+                .map_while(|days| after_days(days).map(|date| celebration(date, days)))
+                .take_while(|c| c.date <= to)
+                .filter(|c| in_range(&c.date))
+                .collect(),
+            Occasion::HalfBirthday => {
+                let first = u32::try_from(from.year() - born.year() - 1).unwrap_or(0);
+                (first..)
+                    .map_while(|years| {
+                        after_months(years * MONTHS_IN_A_YEAR + MONTHS_IN_A_YEAR / 2)
+                            .map(|date| celebration(date, i64::from(years)))
+                    })
+                    .skip_while(|c| c.date < from)
+                    .take_while(|c| c.date <= to)
+                    .collect()
+            }
+            Occasion::Monthsary => (1..MONTHS_IN_A_YEAR)
+                .filter_map(|months| {
+                    after_months(months).map(|date| celebration(date, i64::from(months)))
+                })
+                .filter(|c| in_range(&c.date))
+                .collect(),
+            Occasion::FirstRelease | Occasion::StableRelease => {
+                let release = match occasion {
+                    Occasion::FirstRelease => &self.first_release,
+                    _ => &self.stable_release,
+                };
+                let Some(release) = release else {
+                    return vec![];
+                };
+                yearly(release.date.date(), from, to, policy)
+                    .into_iter()
+                    .map(|(date, years)| celebration(date, years))
+                    .collect()
+            }
+            Occasion::StarMilestone => self
+                .star_milestones
+                .iter()
+                .flat_map(|milestone| {
+                    yearly(milestone.date.date(), from, to, policy)
+                        .into_iter()
+                        .map(|(date, years)| Celebration {
+                            stars: Some(milestone.stars),
+                            ..celebration(date, years)
+                        })
+                })
+                .collect(),
+            Occasion::FirstIssue
+            | Occasion::FirstPullRequest
+            | Occasion::FirstContribution
+            | Occasion::FirstFork => match self.first(occasion) {
+                Some(first) => yearly(first.date.date(), from, to, policy)
+                    .into_iter()
+                    .map(|(date, years)| celebration(date, years))
+                    .collect(),
+                None => vec![],
+            },
+        }
+    }
+
+    /// The community first an occasion celebrates, if it is one of them and it happened.
+    pub fn first(&self, occasion: Occasion) -> Option<&First> {
+        match occasion {
+            Occasion::FirstIssue => self.firsts.issue.as_ref(),
+            Occasion::FirstPullRequest => self.firsts.pull_request.as_ref(),
+            Occasion::FirstContribution => self.firsts.contribution.as_ref(),
+            Occasion::FirstFork => self.firsts.fork.as_ref(),
+            _ => None,
+        }
     }
 }
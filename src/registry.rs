@@ -0,0 +1,81 @@
+use anyhow::{anyhow, Result};
+use chrono::Local;
+use serde::Deserialize;
+use std::borrow::Cow;
+
+use crate::feed::Settings;
+use crate::models::FirstCommit;
+
+/// Version of the column set, bumped whenever columns are added, removed or changed.
+pub const VERSION: &str = "1";
+
+const COLUMNS: [&str; 8] = [
+    "owner",
+    "name",
+    "born",
+    "sha",
+    "age",
+    "next_anniversary",
+    "date_source",
+    "headline",
+];
+
+/// Query string parameters for the CSV export.
+#[derive(Debug, Default, Deserialize)]
+pub struct Version {
+    version: Option<String>,
+}
+
+impl Version {
+    /// Only the current column set is available, but asking for it explicitly protects
+    /// spreadsheets from future changes.
+    pub fn validate(&self) -> Result<()> {
+        match self.version.as_deref().map(str::trim) {
+            None | Some("") | Some(VERSION) => Ok(()),
+            Some(v) => Err(anyhow!(
+                "Invalid value for version, {}: the only version available is {}",
+                v,
+                VERSION
+            )),
+        }
+    }
+}
+
+/// Spreadsheets evaluate cells starting with these as formulas.
+const FORMULA_PREFIXES: [char; 6] = ['=', '+', '-', '@', '\t', '\r'];
+
+/// Text from repos and commits, with a leading `'` when a spreadsheet would take it for a formula.
+fn text(value: &str) -> Cow<'_, str> {
+    if value.starts_with(FORMULA_PREFIXES) {
+        Cow::Owned(format!("'{}", value))
+    } else {
+        Cow::Borrowed(value)
+    }
+}
+
+/// One row per repository kept by the settings, oldest first.
+pub fn csv_from(commits: &[FirstCommit], settings: &Settings) -> Result<String> {
+    let today = Local::now().date_naive();
+    let mut commits: Vec<&FirstCommit> = commits
+        .iter()
+        .filter(|commit| settings.keeps(commit))
+        .collect();
+    commits.sort_by_key(|commit| commit.date);
+    let mut writer = csv::Writer::from_writer(vec![]);
+    writer.write_record(COLUMNS)?;
+    for commit in commits {
+        let next_anniversary = commit.next_anniversary(settings.leap_day)?;
+        let age = commit.age_on(today, settings.leap_day)?;
+        writer.write_record([
+            &text(&commit.owner),
+            &text(&commit.name),
+            commit.date.format("%Y-%m-%d").to_string().as_str(),
+            commit.sha.as_str(),
+            age.to_string().as_str(),
+            next_anniversary.format("%Y-%m-%d").to_string().as_str(),
+            commit.date_source(),
+            &text(commit.headline()),
+        ])?;
+    }
+    Ok(String::from_utf8(writer.into_inner()?)?)
+}
@@ -1,4 +1,4 @@
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use serde::Deserialize;
 
 use crate::graphql::GitHubGraphQL;
@@ -8,12 +8,45 @@ pub struct Owner {
     pub login: String,
 }
 
+#[derive(Clone, Debug, Deserialize)]
+pub struct Language {
+    pub name: String,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Topic {
+    pub name: String,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct DefaultBranch {
+    pub name: String,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct TopicNode {
+    pub topic: Topic,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Topics {
+    pub nodes: Vec<TopicNode>,
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Repository {
     pub name: String,
     pub is_fork: bool,
+    pub is_private: bool,
+    pub is_empty: bool,
+    pub default_branch_ref: Option<DefaultBranch>,
     pub owner: Owner,
+    pub url: String,
+    pub description: Option<String>,
+    pub stargazer_count: u32,
+    pub primary_language: Option<Language>,
+    pub repository_topics: Topics,
 }
 
 impl Clone for Repository {
@@ -21,35 +54,50 @@ impl Clone for Repository {
         Self {
             name: self.name.clone(),
             is_fork: self.is_fork,
+            is_private: self.is_private,
+            is_empty: self.is_empty,
+            default_branch_ref: self.default_branch_ref.clone(),
             owner: Owner {
                 login: self.owner.login.clone(),
             },
+            url: self.url.clone(),
+            description: self.description.clone(),
+            stargazer_count: self.stargazer_count,
+            primary_language: self.primary_language.clone(),
+            repository_topics: self.repository_topics.clone(),
         }
     }
 }
 
+impl Repository {
+    pub fn topics(&self) -> Vec<String> {
+        self.repository_topics
+            .nodes
+            .iter()
+            .map(|node| node.topic.name.clone())
+            .collect()
+    }
+}
+
 impl std::fmt::Display for Repository {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}/{}", self.owner.login, self.name)
     }
 }
 
-pub async fn repos_for(client: &GitHubGraphQL, username: &str) -> Result<Vec<Repository>> {
+async fn fetch_repos(
+    client: &GitHubGraphQL,
+    username: &str,
+    public_only: bool,
+) -> Result<Vec<Repository>> {
     let mut repos: Vec<Repository> = vec![];
     let mut has_next_page = true;
     let mut cursor = "".to_string();
 
     while has_next_page {
-        let response = client.repos(username, cursor.as_str()).await?;
+        let response = client.repos(username, cursor.as_str(), public_only).await?;
         let body: Response = serde_json::from_str(&response)?;
-        repos.extend(
-            body.data
-                .user
-                .repositories
-                .nodes
-                .into_iter()
-                .filter(|r| !r.is_fork),
-        );
+        repos.extend(body.data.user.repositories.nodes);
         has_next_page = body.data.user.repositories.page_info.has_next_page;
         cursor = body.data.user.repositories.page_info.end_cursor.clone();
     }
@@ -57,6 +105,85 @@ pub async fn repos_for(client: &GitHubGraphQL, username: &str) -> Result<Vec<Rep
     Ok(repos)
 }
 
+pub async fn repos_for(client: &GitHubGraphQL, username: &str) -> Result<Vec<Repository>> {
+    let repos = fetch_repos(client, username, true).await?;
+    Ok(repos.into_iter().filter(|r| !r.is_fork).collect())
+}
+
+/// Repositories of an organization, leaving out forks like `repos_for`. Private ones are only
+/// listed when `public_only` is false and the token can see them.
+pub async fn org_repos_for(
+    client: &GitHubGraphQL,
+    org: &str,
+    public_only: bool,
+) -> Result<Vec<Repository>> {
+    let mut repos: Vec<Repository> = vec![];
+    let mut has_next_page = true;
+    let mut cursor = "".to_string();
+
+    while has_next_page {
+        let response = client.org_repos(org, cursor.as_str(), public_only).await?;
+        let body: OrganizationResponse = serde_json::from_str(&response)?;
+        let organization = body
+            .data
+            .organization
+            .ok_or_else(|| anyhow!("Organization {} not found", org))?;
+        repos.extend(organization.repositories.nodes);
+        has_next_page = organization.repositories.page_info.has_next_page;
+        cursor = organization.repositories.page_info.end_cursor;
+    }
+
+    Ok(repos.into_iter().filter(|r| !r.is_fork).collect())
+}
+
+/// How the owner of the token relates to the organization, `None` if it is not visible to them.
+pub async fn membership(client: &GitHubGraphQL, org: &str) -> Result<Option<Membership>> {
+    let response = client.membership(org).await?;
+    let body: MembershipResponse = serde_json::from_str(&response)?;
+    Ok(body.data.organization)
+}
+
+/// Whether the owner of the token is a member of the organization, private memberships included.
+pub async fn is_member(client: &GitHubGraphQL, org: &str) -> Result<bool> {
+    Ok(membership(client, org)
+        .await?
+        .is_some_and(|organization| organization.viewer_is_a_member))
+}
+
+pub fn validate_owner(owner: &str) -> Result<()> {
+    let valid_owner = |c: char| c.is_ascii_alphanumeric() || c == '-';
+    if owner.is_empty() || owner.len() > 39 || !owner.chars().all(valid_owner) {
+        return Err(anyhow!("Invalid owner {}", owner));
+    }
+    Ok(())
+}
+
+/// Owner and repository names go into GraphQL queries, so only characters GitHub allows are.
+pub fn validate_name(owner: &str, name: &str) -> Result<()> {
+    validate_owner(owner)?;
+    let valid_name = |c: char| c.is_ascii_alphanumeric() || "-_.".contains(c);
+    if name.is_empty() || name.len() > 100 || !name.chars().all(valid_name) {
+        return Err(anyhow!("Invalid repository name {}", name));
+    }
+    Ok(())
+}
+
+/// A single repository, `None` if it does not exist or is not visible with this token.
+pub async fn repo_for(
+    client: &GitHubGraphQL,
+    owner: &str,
+    name: &str,
+) -> Result<Option<Repository>> {
+    let response = client.repository(name, owner).await?;
+    let body: RepositoryResponse = serde_json::from_str(&response)?;
+    Ok(body.data.repository)
+}
+
+/// Every repository GitHub returns for the user, including the ones left out of calendars.
+pub async fn all_repos_for(client: &GitHubGraphQL, username: &str) -> Result<Vec<Repository>> {
+    fetch_repos(client, username, false).await
+}
+
 #[derive(Debug, Deserialize)]
 pub struct PageInfo {
     #[serde(rename = "endCursor")]
@@ -88,3 +215,45 @@ pub struct Data {
 pub struct Response {
     pub data: Data,
 }
+
+#[derive(Debug, Deserialize)]
+pub struct RepositoryData {
+    pub repository: Option<Repository>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RepositoryResponse {
+    pub data: RepositoryData,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Organization {
+    pub repositories: Repositories,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct OrganizationData {
+    pub organization: Option<Organization>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct OrganizationResponse {
+    pub data: OrganizationData,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Membership {
+    pub viewer_is_a_member: bool,
+    pub viewer_can_administer: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MembershipData {
+    pub organization: Option<Membership>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MembershipResponse {
+    pub data: MembershipData,
+}
@@ -0,0 +1,122 @@
+use anyhow::Result;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+use crate::date_time_serializer;
+use crate::envvar;
+use crate::graphql::GitHubGraphQL;
+use crate::models::{FirstCommit, StarMilestone};
+
+const STAR_THRESHOLDS: [u32; 10] = [
+    100, 250, 500, 1_000, 2_500, 5_000, 10_000, 25_000, 50_000, 100_000,
+];
+const DEFAULT_MIN_STARS: u32 = 100;
+// 100 stargazers per page: popular repos catch up over a few generations
+const MAX_PAGES: usize = 50;
+
+/// How far the stargazers of a repo were paged through, cached so later generations only fetch
+/// the new ones.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct StarHistory {
+    pub cursor: Option<String>,
+    pub seen: u32,
+    pub milestones: Vec<StarMilestone>,
+}
+
+/// Repos with fewer stars than `STARGAZERS_MIN_STARS` (100 by default) are not paged through.
+pub fn min_stars() -> u32 {
+    envvar::get("STARGAZERS_MIN_STARS")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(DEFAULT_MIN_STARS)
+}
+
+/// Continues paging through the stargazers of a repo, oldest first, recording when each
+/// threshold was crossed. The history is returned even when a page fails, along with the error,
+/// so the pages already read are not lost.
+pub async fn star_history(
+    client: &GitHubGraphQL,
+    commit: &FirstCommit,
+    mut history: StarHistory,
+) -> (StarHistory, Result<()>) {
+    let next = STAR_THRESHOLDS
+        .iter()
+        .find(|threshold| **threshold > history.seen);
+    if next.is_none_or(|threshold| commit.stars < *threshold) {
+        return (history, Ok(()));
+    }
+
+    for _ in 0..MAX_PAGES {
+        let cursor = history.cursor.clone().unwrap_or_default();
+        let stargazers = match page(client, &commit.owner, &commit.name, &cursor).await {
+            Ok(stargazers) => stargazers,
+            Err(e) => return (history, Err(e)),
+        };
+        for edge in stargazers.edges {
+            history.seen += 1;
+            if STAR_THRESHOLDS.contains(&history.seen) {
+                history.milestones.push(StarMilestone {
+                    stars: history.seen,
+                    date: edge.starred_at,
+                });
+            }
+        }
+        if let Some(cursor) = stargazers.page_info.end_cursor {
+            history.cursor = Some(cursor);
+        }
+        if !stargazers.page_info.has_next_page {
+            break;
+        }
+    }
+    (history, Ok(()))
+}
+
+async fn page(client: &GitHubGraphQL, owner: &str, name: &str, cursor: &str) -> Result<Stargazers> {
+    let resp = client.stargazers(name, owner, cursor).await?;
+    let contents: Response = serde_json::from_str(&resp).map_err(|e| {
+        anyhow::anyhow!(
+            "Error parsing stargazers of {owner}/{name}: {}\n{}",
+            e,
+            resp
+        )
+    })?;
+    Ok(contents.data.repository.stargazers)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PageInfo {
+    #[serde(rename = "endCursor")]
+    pub end_cursor: Option<String>,
+
+    #[serde(rename = "hasNextPage")]
+    pub has_next_page: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Edge {
+    #[serde(with = "date_time_serializer", rename = "starredAt")]
+    pub starred_at: NaiveDateTime,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Stargazers {
+    pub edges: Vec<Edge>,
+
+    #[serde(rename = "pageInfo")]
+    pub page_info: PageInfo,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Repository {
+    pub stargazers: Stargazers,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Data {
+    pub repository: Repository,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Response {
+    pub data: Data,
+}
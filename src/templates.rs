@@ -1,24 +1,49 @@
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use lazy_static::lazy_static;
-use liquid::{ParserBuilder, Template};
+use liquid::{Parser, ParserBuilder, Template};
+use liquid_lib::stdlib;
 
 const REPOS_QUERY: &str = include_str!("graphql/repos.graphql");
 const CURSOR_QUERY: &str = include_str!("graphql/cursor.graphql");
 const LAST_COMMIT_QUERY: &str = include_str!("graphql/last_commit.graphql");
+const STARGAZERS_QUERY: &str = include_str!("graphql/stargazers.graphql");
+const FIRSTS_QUERY: &str = include_str!("graphql/firsts.graphql");
+const REPOSITORY_QUERY: &str = include_str!("graphql/repository.graphql");
+const RELEASES_QUERY: &str = include_str!("graphql/releases.graphql");
+const MERGED_QUERY: &str = include_str!("graphql/merged.graphql");
+const ORG_REPOS_QUERY: &str = include_str!("graphql/org_repos.graphql");
+const MEMBERSHIP_QUERY: &str = include_str!("graphql/membership.graphql");
 const INDEX_HTML: &str = include_str!("html/index.html");
+const CALENDAR_HTML: &str = include_str!("html/calendar.html");
+const EXPLAIN_HTML: &str = include_str!("html/explain.html");
+const TIMELINE_HTML: &str = include_str!("html/timeline.html");
+const ORG_HTML: &str = include_str!("html/org.html");
+const MAX_USER_TEMPLATE_LENGTH: usize = 1000;
 
 lazy_static! {
     pub static ref TEMPLATES: Templates = Templates::new().expect("Error building templates");
+    static ref USER_PARSER: Parser = user_parser().expect("Error building user template parser");
 }
 
 pub struct HTMLTemplates {
     pub home: Template,
+    pub calendar: Template,
+    pub explain: Template,
+    pub timeline: Template,
+    pub org: Template,
 }
 
 pub struct GraphQLTemplates {
     pub repos: Template,
     pub cursor: Template,
     pub last_commit: Template,
+    pub stargazers: Template,
+    pub firsts: Template,
+    pub repository: Template,
+    pub releases: Template,
+    pub merged: Template,
+    pub org_repos: Template,
+    pub membership: Template,
 }
 
 pub struct Templates {
@@ -32,12 +57,78 @@ impl Templates {
         Ok(Self {
             html: HTMLTemplates {
                 home: parser.parse(INDEX_HTML)?,
+                calendar: parser.parse(CALENDAR_HTML)?,
+                explain: parser.parse(EXPLAIN_HTML)?,
+                timeline: parser.parse(TIMELINE_HTML)?,
+                org: parser.parse(ORG_HTML)?,
             },
             graphql: GraphQLTemplates {
                 repos: parser.parse(REPOS_QUERY)?,
                 cursor: parser.parse(CURSOR_QUERY)?,
                 last_commit: parser.parse(LAST_COMMIT_QUERY)?,
+                stargazers: parser.parse(STARGAZERS_QUERY)?,
+                firsts: parser.parse(FIRSTS_QUERY)?,
+                repository: parser.parse(REPOSITORY_QUERY)?,
+                releases: parser.parse(RELEASES_QUERY)?,
+                merged: parser.parse(MERGED_QUERY)?,
+                org_repos: parser.parse(ORG_REPOS_QUERY)?,
+                membership: parser.parse(MEMBERSHIP_QUERY)?,
             },
         })
     }
 }
+
+/// Parser for templates written by users: loops, cycles, partials and variables are left out, and
+/// so are the filters that could grow a string over and over (`append`, `prepend`, `replace` and
+/// `split`, which `join` could glue back together).
+fn user_parser() -> Result<Parser> {
+    Ok(ParserBuilder::new()
+        .tag(stdlib::IncrementTag)
+        .tag(stdlib::DecrementTag)
+        .block(stdlib::RawBlock)
+        .block(stdlib::IfBlock)
+        .block(stdlib::UnlessBlock)
+        .block(stdlib::CommentBlock)
+        .block(stdlib::CaseBlock)
+        .filter(stdlib::Abs)
+        .filter(stdlib::AtLeast)
+        .filter(stdlib::AtMost)
+        .filter(stdlib::Capitalize)
+        .filter(stdlib::Ceil)
+        .filter(stdlib::Date)
+        .filter(stdlib::Default)
+        .filter(stdlib::DividedBy)
+        .filter(stdlib::Downcase)
+        .filter(stdlib::First)
+        .filter(stdlib::Floor)
+        .filter(stdlib::Join)
+        .filter(stdlib::Last)
+        .filter(stdlib::Lstrip)
+        .filter(stdlib::Minus)
+        .filter(stdlib::Modulo)
+        .filter(stdlib::Plus)
+        .filter(stdlib::Remove)
+        .filter(stdlib::RemoveFirst)
+        .filter(stdlib::ReplaceFirst)
+        .filter(stdlib::Round)
+        .filter(stdlib::Rstrip)
+        .filter(stdlib::Size)
+        .filter(stdlib::Slice)
+        .filter(stdlib::Strip)
+        .filter(stdlib::StripNewlines)
+        .filter(stdlib::Times)
+        .filter(stdlib::Truncate)
+        .filter(stdlib::TruncateWords)
+        .filter(stdlib::Upcase)
+        .build()?)
+}
+
+pub fn parse_user_template(source: &str) -> Result<Template> {
+    if source.len() > MAX_USER_TEMPLATE_LENGTH {
+        return Err(anyhow!(
+            "Templates cannot be longer than {} characters",
+            MAX_USER_TEMPLATE_LENGTH
+        ));
+    }
+    Ok(USER_PARSER.parse(source)?)
+}
@@ -1,23 +1,52 @@
 use crate::{
-    auth::{token_for, username_for},
-    cache::CACHE,
+    atom::{feed_from, Trailing},
+    auth::{hash, random_token, scopes_for, token_for, username_for},
+    badge::{age, svg},
+    cache::{Scope, CACHE, SESSION_TTL},
     calendar::calendar_from,
-    commits::last_commit,
+    commits::{last_commit, releases},
+    dashboard::{table, TableParams},
     envvar,
+    explain::{explain, rows, Entry},
+    feed::{
+        leap_day_param, parse_lang, parse_leap_day, validate_name, Params, Preferences, Settings,
+    },
+    firsts::{firsts, timeline},
+    formats::Format,
     graphql::GitHubGraphQL,
-    repositories::repos_for,
+    grid::{grid, validate_month, validate_year},
+    i18n::{from_accept_language, languages, DEFAULT_LANGUAGE, I18N},
+    jobs::{job, Job, Status, Tracker},
+    json::{upcoming_from, Pagination},
+    models::{Failure, FirstCommit, Firsts, Occasion, OrgRegistration, StarMilestone},
+    registry::{csv_from, Version, VERSION as CSV_VERSION},
+    repositories::{
+        all_repos_for, is_member, membership, org_repos_for, repo_for, repos_for,
+        validate_name as validate_repo_name, validate_owner, Repository,
+    },
+    stargazers::{min_stars, star_history},
     templates::TEMPLATES,
 };
 use actix_web::{
-    error::{ErrorInternalServerError, ErrorNotFound},
+    cookie::{self, Cookie, SameSite},
+    error::{ErrorBadRequest, ErrorForbidden, ErrorInternalServerError, ErrorNotFound},
     get,
-    http::{header::ContentType, StatusCode},
+    http::{
+        header::{self, ContentType},
+        StatusCode,
+    },
+    post,
     web::{self, Redirect},
-    Error, HttpResponse, Responder,
+    Error, HttpRequest, HttpResponse, Responder,
 };
+use chrono::{Datelike, Local};
+use fluent_bundle::FluentArgs;
+use futures_util::stream;
 use reqwest::Client;
 use serde::Deserialize;
+use std::collections::HashSet;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::Semaphore;
 
 #[derive(Deserialize)]
@@ -25,34 +54,323 @@ struct CallbackParams {
     code: Option<String>,
 }
 
+#[derive(Deserialize)]
+struct PreferencesForm {
+    public: Option<String>,
+    lang: Option<String>,
+    leap_day: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ExplainParams {
+    feed: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct OrgForm {
+    org: String,
+}
+
+#[derive(Deserialize)]
+struct FeedForm {
+    name: String,
+
+    #[serde(flatten)]
+    params: Params,
+}
+
 pub const DEFAULT_PORT: u16 = 8000;
 pub const DEFAULT_IP: &str = "0.0.0.0";
+const SESSION_COOKIE: &str = "session";
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(1);
+const RENDER_TIMEOUT: Duration = Duration::from_secs(5);
+const ORG_SCOPES: [&str; 3] = ["read:org", "write:org", "admin:org"];
+const PRIVATE_REPOS_SCOPE: &str = "repo";
 
-async fn data_for(token: &str, username: &str) -> anyhow::Result<String> {
-    let client = GitHubGraphQL::new(token);
-    let repos = repos_for(&client, username).await?;
+/// When popular repos reached round numbers of stars. Errors only cost the star milestones, not
+/// the whole repo.
+async fn star_milestones_for(client: &GitHubGraphQL, commit: &FirstCommit) -> Vec<StarMilestone> {
+    if commit.stars < min_stars() {
+        return vec![];
+    }
+    let (owner, name) = (commit.owner.as_str(), commit.name.as_str());
+    let result = async {
+        let history = CACHE.star_history(owner, name).await?;
+        let (history, result) = star_history(client, commit, history).await;
+        CACHE.save_star_history(owner, name, &history).await?;
+        if let Err(e) = result {
+            eprintln!(
+                "Error paging through the stargazers of {}/{}: {}",
+                owner, name, e
+            );
+        }
+        Ok::<_, anyhow::Error>(history.milestones)
+    };
+    result.await.unwrap_or_else(|e| {
+        eprintln!("Error finding star milestones of {}/{}: {}", owner, name, e);
+        vec![]
+    })
+}
+
+/// Community firsts from the cache, or from GitHub. Errors only cost the firsts, not the whole repo.
+async fn firsts_for(client: &GitHubGraphQL, commit: &FirstCommit) -> Firsts {
+    let (owner, name) = (commit.owner.as_str(), commit.name.as_str());
+    let result = async {
+        if let Some(cached) = CACHE.firsts(owner, name).await? {
+            return Ok(cached);
+        }
+        let found = firsts(client, owner, name).await?;
+        CACHE.save_firsts(owner, name, &found).await?;
+        Ok::<_, anyhow::Error>(found)
+    };
+    result.await.unwrap_or_else(|e| {
+        eprintln!("Error finding the firsts of {}/{}: {}", owner, name, e);
+        Firsts::default()
+    })
+}
+
+/// Releases of a repo from GitHub. Errors only cost the releases, not the whole repo.
+async fn with_releases(client: &GitHubGraphQL, mut commit: FirstCommit) -> FirstCommit {
+    match releases(client, &commit).await {
+        Ok((first, stable)) => {
+            commit.first_release = first;
+            commit.stable_release = stable;
+        }
+        Err(e) => eprintln!(
+            "Error finding the releases of {}/{}: {}",
+            commit.owner, commit.name, e
+        ),
+    }
+    commit
+}
+
+/// Releases, star milestones and community firsts take extra requests per repo, so they are only
+/// looked for when the settings celebrate them, right before rendering.
+async fn with_extras(
+    token: &str,
+    commits: Vec<FirstCommit>,
+    settings: &Settings,
+) -> Vec<FirstCommit> {
+    let releases = settings
+        .occasions
+        .iter()
+        .any(|occasion| matches!(occasion, Occasion::FirstRelease | Occasion::StableRelease));
+    let firsts = settings.occasions.iter().any(Occasion::is_first);
+    let stars = settings.occasions.contains(&Occasion::StarMilestone);
+    if !releases && !firsts && !stars {
+        return commits;
+    }
+    let semaphore = Arc::new(Semaphore::new(16));
+    let mut results = Vec::with_capacity(commits.len());
+    for mut commit in commits.into_iter() {
+        let tkn = token.to_string();
+        let sem = semaphore.clone();
+        results.push(tokio::spawn(async move {
+            let _permit = sem.acquire().await.unwrap();
+            let client = GitHubGraphQL::new(tkn.as_ref());
+            if releases {
+                commit = with_releases(&client, commit).await;
+            }
+            if stars {
+                commit.star_milestones = star_milestones_for(&client, &commit).await;
+            }
+            if firsts {
+                commit.firsts = firsts_for(&client, &commit).await;
+            }
+            commit
+        }));
+    }
+    let mut commits = Vec::with_capacity(results.len());
+    for result in results {
+        match result.await {
+            Ok(commit) => commits.push(commit),
+            Err(e) => eprintln!("Error looking for the extras of a repo: {}", e),
+        }
+    }
+    commits
+}
+
+/// Finds the first commit of each repository, keeping aside the ones that could not be read.
+async fn first_commits(
+    token: &str,
+    repos: Vec<Repository>,
+    tracker: Option<&Tracker>,
+) -> (Vec<FirstCommit>, Vec<Failure>) {
     let total = repos.len();
     let semaphore = Arc::new(Semaphore::new(16));
     let mut results = Vec::with_capacity(total);
     for repo in repos.into_iter() {
         let tkn = token.to_string();
         let sem = semaphore.clone();
+        let tracker = tracker.cloned();
+        let name = repo.to_string();
         let result = tokio::spawn(async move {
             let _permit = sem.acquire().await.unwrap();
             let client = GitHubGraphQL::new(tkn.as_ref());
-            last_commit(&client, &repo).await
+            let result = last_commit(&client, &repo).await;
+            if let Some(tracker) = tracker {
+                match &result {
+                    Ok(_) => tracker.resolved(),
+                    Err(e) => tracker.failed(Failure::new(repo.to_string(), e)),
+                }
+            }
+            result
         });
-        results.push(result);
+        results.push((name, result));
     }
+
+    // one weird repo should not leave users without a calendar, so failures are kept aside
     let mut commits = Vec::with_capacity(total);
-    for result in results {
-        if let Some(commit) = result.await?? {
-            commits.push(commit);
+    let mut failures = vec![];
+    for (name, result) in results {
+        match result.await {
+            Ok(Ok(Some(commit))) => commits.push(commit),
+            Ok(Ok(None)) => {}
+            Ok(Err(e)) => failures.push(Failure::new(name, e)),
+            Err(e) => failures.push(Failure::new(name, e)),
         }
     }
-    commits.sort_by_key(|commit| commit.days_to_next_anniversary());
-    let contents = format!("{}", calendar_from(username, &commits));
-    CACHE.save_calendar(username, contents.as_ref()).await?;
+    for failure in &failures {
+        eprintln!("Error finding the first commit of {}", failure);
+    }
+    (commits, failures)
+}
+
+/// Fetches the first commit of every repository from GitHub, and caches them.
+async fn fetch_commits(
+    token: &str,
+    username: &str,
+    tracker: Option<&Tracker>,
+) -> anyhow::Result<Vec<FirstCommit>> {
+    let client = GitHubGraphQL::new(token);
+    let repos = repos_for(&client, username).await?;
+    if let Some(tracker) = tracker {
+        tracker.listed(repos.len());
+    }
+    let (commits, failures) = first_commits(token, repos, tracker).await;
+    CACHE.save_commits(username, &commits).await?;
+    CACHE.save_failures(username, &failures).await?;
+    Ok(commits)
+}
+
+/// Fetches the first commits of an organization's repos with the token of the member who
+/// registered it, caching the public ones apart from the whole list.
+async fn fetch_org_commits(
+    org: &str,
+    registration: &OrgRegistration,
+    private: bool,
+) -> anyhow::Result<Vec<FirstCommit>> {
+    let token = CACHE.token(&registration.member).await?;
+    let client = GitHubGraphQL::new(token.as_ref());
+    if !is_member(&client, org).await? {
+        return Err(anyhow::anyhow!(
+            "{} is no longer a member of {}",
+            registration.member,
+            org
+        ));
+    }
+    let repos = org_repos_for(&client, org, !registration.private).await?;
+    let private_repos: HashSet<String> = repos
+        .iter()
+        .filter(|repo| repo.is_private)
+        .map(|repo| repo.name.clone())
+        .collect();
+    let (commits, _) = first_commits(token.as_ref(), repos, None).await;
+    let public: Vec<FirstCommit> = commits
+        .iter()
+        .filter(|commit| !private_repos.contains(&commit.name))
+        .cloned()
+        .collect();
+    CACHE.save_org_commits(org, false, &public).await?;
+    CACHE.save_org_commits(org, true, &commits).await?;
+    Ok(if private { commits } else { public })
+}
+
+async fn commits_for(
+    token: &str,
+    username: &str,
+    tracker: Option<&Tracker>,
+) -> anyhow::Result<Vec<FirstCommit>> {
+    match CACHE.commits(username).await {
+        Ok(commits) => Ok(commits),
+        Err(_) => fetch_commits(token, username, tracker).await,
+    }
+}
+
+/// Runs CPU-bound work, such as rendering user templates, out of the async workers. Past the
+/// timeout the request fails, although the thread itself cannot be stopped.
+async fn blocking<T: Send + 'static>(
+    f: impl FnOnce() -> anyhow::Result<T> + Send + 'static,
+) -> anyhow::Result<T> {
+    match tokio::time::timeout(RENDER_TIMEOUT, tokio::task::spawn_blocking(f)).await {
+        Ok(result) => result?,
+        Err(_) => Err(anyhow::anyhow!(
+            "Rendering took longer than {} seconds",
+            RENDER_TIMEOUT.as_secs()
+        )),
+    }
+}
+
+/// Checks the templates of the settings, only to be called after the access checks.
+async fn validated(settings: Settings) -> Result<Settings, Error> {
+    let sample = settings.clone();
+    blocking(move || sample.validate_templates())
+        .await
+        .map_err(ErrorBadRequest)?;
+    Ok(settings)
+}
+
+/// Renders a calendar out of the async workers, since it runs the user templates.
+async fn render_calendar(
+    name: &str,
+    commits: Vec<FirstCommit>,
+    failures: Vec<Failure>,
+    settings: &Settings,
+) -> anyhow::Result<String> {
+    let name = name.to_string();
+    let settings = settings.clone();
+    blocking(move || {
+        Ok(format!(
+            "{}",
+            calendar_from(&name, &commits, &failures, &settings)?
+        ))
+    })
+    .await
+}
+
+/// Feeds that do not set a language use the user's preferred one.
+async fn with_preferred_lang(username: &str, settings: &Settings) -> anyhow::Result<Settings> {
+    let mut settings = settings.clone();
+    if settings.lang.is_none() {
+        settings.lang = CACHE.preferences(username).await?.lang;
+    }
+    Ok(settings)
+}
+
+async fn data_for(
+    token: &str,
+    username: &str,
+    settings: &Settings,
+    scope: &Scope,
+    tracker: Option<&Tracker>,
+) -> anyhow::Result<String> {
+    let settings = &with_preferred_lang(username, settings).await?;
+    let commits = commits_for(token, username, tracker).await?;
+    let mut commits = with_extras(token, commits, settings).await;
+    commits.sort_by_cached_key(|commit| {
+        commit
+            .days_to_next_anniversary(settings.leap_day)
+            .unwrap_or(i64::MAX)
+    });
+    let failures = if settings.failures {
+        CACHE.failures(username).await?
+    } else {
+        vec![]
+    };
+    let contents = render_calendar(username, commits, failures, settings).await?;
+    CACHE
+        .save_calendar(username, scope, contents.as_ref())
+        .await?;
     Ok(contents)
 }
 
@@ -61,7 +379,7 @@ fn log_and_crash(error: impl std::fmt::Display) -> Error {
     ErrorInternalServerError("Internal server error")
 }
 
-fn context(username: Option<&String>) -> anyhow::Result<liquid::Object> {
+fn base_url() -> String {
     let domain = envvar::get("DOMAIN").unwrap_or(format!(
         "{}:{}",
         DEFAULT_IP,
@@ -72,19 +390,47 @@ fn context(username: Option<&String>) -> anyhow::Result<liquid::Object> {
     } else {
         "https"
     };
+    format!("{}://{}", protocol, domain)
+}
+
+fn lang_for(req: &HttpRequest) -> &'static str {
+    let header = req
+        .headers()
+        .get(header::ACCEPT_LANGUAGE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or("");
+    from_accept_language(header)
+}
+
+fn context(username: Option<&String>, lang: &str) -> anyhow::Result<liquid::Object> {
     Ok(liquid::object!({
-        "url": format!("{}://{}",protocol, domain),
+        "url": base_url(),
         "username": username,
         "client_id": envvar::get("GITHUB_APP_CLIENT_ID")?,
+        "lang": lang,
+        "t": I18N.html(lang),
+        "languages": languages(),
     }))
 }
 
+async fn logged_in_as(req: &HttpRequest) -> Option<String> {
+    let cookie = req.cookie(SESSION_COOKIE)?;
+    CACHE.session(cookie.value()).await.ok()
+}
+
+async fn require_owner(req: &HttpRequest, username: &str) -> Result<(), Error> {
+    match logged_in_as(req).await {
+        Some(user) if user == username => Ok(()),
+        _ => Err(ErrorForbidden("Forbidden")),
+    }
+}
+
 #[get("/")]
-async fn index() -> Result<impl Responder, Error> {
+async fn index(req: HttpRequest) -> Result<impl Responder, Error> {
     TEMPLATES
         .html
         .home
-        .render(&context(None).map_err(log_and_crash)?)
+        .render(&context(None, lang_for(&req)).map_err(log_and_crash)?)
         .map(|html| {
             HttpResponse::build(StatusCode::OK)
                 .content_type(ContentType::html())
@@ -103,63 +449,1041 @@ async fn callback(info: web::Query<CallbackParams>) -> Result<impl Responder, Er
             .save_token(&username, token.as_ref())
             .await
             .map_err(log_and_crash)?;
-        Ok(Redirect::to(format!("/{}", username)))
+        let session = random_token();
+        CACHE
+            .save_session(&session, &username)
+            .await
+            .map_err(log_and_crash)?;
+        let cookie = Cookie::build(SESSION_COOKIE, session)
+            .path("/")
+            .http_only(true)
+            .secure(base_url().starts_with("https"))
+            .same_site(SameSite::Lax)
+            .max_age(cookie::time::Duration::seconds(SESSION_TTL.num_seconds()))
+            .finish();
+        Ok(HttpResponse::Found()
+            .insert_header((header::LOCATION, format!("/{}", username)))
+            .cookie(cookie)
+            .finish())
     } else {
         Err(log_and_crash("Missing code in GitHub callback"))
     }
 }
 
-async fn _calendar(username: web::Path<String>) -> Result<impl Responder, Error> {
-    if let Ok(contents) = CACHE.calendar(username.as_ref()).await {
-        return Ok(HttpResponse::build(StatusCode::OK)
-            .content_type("text/calendar")
-            .body(contents));
+fn format_for(req: &HttpRequest, extension: &str) -> Format {
+    let accept = req
+        .headers()
+        .get(header::ACCEPT)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or("");
+    Format::negotiate(extension, accept)
+}
+
+fn calendar_response(contents: String, format: Format) -> Result<HttpResponse, Error> {
+    let body = format.render(contents).map_err(log_and_crash)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .content_type(format.content_type())
+        .body(body))
+}
+
+async fn serve_calendar(
+    username: &str,
+    settings: &Settings,
+    scope: &Scope,
+    format: Format,
+) -> Result<HttpResponse, Error> {
+    if let Ok(contents) = CACHE.calendar(username, scope).await {
+        return calendar_response(contents, format);
     }
-    if let Ok(token) = CACHE.token(username.as_ref()).await {
-        return data_for(token.as_ref(), username.as_ref())
+    if let Ok(token) = CACHE.token(username).await {
+        let settings = &validated(settings.clone()).await?;
+        let contents = data_for(token.as_ref(), username, settings, scope, None)
             .await
-            .map(|contents| {
-                HttpResponse::build(StatusCode::OK)
-                    .content_type("text/calendar")
-                    .body(contents)
-            })
-            .map_err(log_and_crash);
+            .map_err(log_and_crash)?;
+        return calendar_response(contents, format);
     }
     Err(ErrorNotFound("Not found"))
 }
 
+/// Calendars customized through the URL that follow the preferred language are cached per
+/// language, so changing it does not serve them in the previous one.
+async fn scope_for(username: &str, settings: &Settings) -> Result<Scope, Error> {
+    match Scope::from(settings) {
+        Scope::Variant(variant) if settings.lang.is_none() => {
+            let preferences = CACHE.preferences(username).await.map_err(log_and_crash)?;
+            let lang = preferences.lang.as_deref().unwrap_or(DEFAULT_LANGUAGE);
+            Ok(Scope::Variant(format!("{}-{}", variant, lang)))
+        }
+        scope => Ok(scope),
+    }
+}
+
+/// Public URLs do not include the user's secret, and users can choose not to serve them.
+async fn require_public(username: &str) -> Result<(), Error> {
+    let preferences = CACHE.preferences(username).await.map_err(log_and_crash)?;
+    if preferences.public {
+        Ok(())
+    } else {
+        Err(ErrorNotFound("Not found"))
+    }
+}
+
+async fn require_secret(username: &str, secret: &str) -> Result<(), Error> {
+    match CACHE.secret(username).await.map_err(log_and_crash)? {
+        Some(expected) if expected == hash(secret) => Ok(()),
+        _ => Err(ErrorNotFound("Not found")),
+    }
+}
+
+/// First commits from the cache, or from GitHub for users who logged in.
+async fn cached_commits(username: &str) -> Result<Vec<FirstCommit>, Error> {
+    if let Ok(commits) = CACHE.commits(username).await {
+        return Ok(commits);
+    }
+    match CACHE.token(username).await {
+        Ok(token) => fetch_commits(token.as_ref(), username, None)
+            .await
+            .map_err(log_and_crash),
+        Err(_) => Err(ErrorNotFound("Not found")),
+    }
+}
+
+async fn serve_json(
+    username: &str,
+    settings: &Settings,
+    pagination: &Pagination,
+) -> Result<HttpResponse, Error> {
+    let (page, per_page) = pagination.parse().map_err(ErrorBadRequest)?;
+    let commits = cached_commits(username).await?;
+    let upcoming = upcoming_from(&commits, settings, page, per_page).map_err(log_and_crash)?;
+    Ok(HttpResponse::Ok().json(upcoming))
+}
+
+async fn serve_atom(
+    username: &str,
+    settings: &Settings,
+    trailing: &Trailing,
+    url: String,
+) -> Result<HttpResponse, Error> {
+    let days = trailing.parse().map_err(ErrorBadRequest)?;
+    let settings = validated(settings.clone()).await?;
+    let commits = cached_commits(username).await?;
+    let settings = with_preferred_lang(username, &settings)
+        .await
+        .map_err(log_and_crash)?;
+    let name = username.to_string();
+    let feed = blocking(move || Ok(feed_from(&name, &url, &commits, &settings, days)?.to_string()))
+        .await
+        .map_err(log_and_crash)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .content_type("application/atom+xml")
+        .body(feed))
+}
+
+async fn serve_registry(username: &str, settings: &Settings) -> Result<HttpResponse, Error> {
+    let commits = cached_commits(username).await?;
+    let contents = csv_from(&commits, settings).map_err(log_and_crash)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .content_type("text/csv; charset=utf-8")
+        .insert_header((
+            header::CONTENT_DISPOSITION,
+            format!(
+                "attachment; filename=\"{}-repo-birthdays-v{}.csv\"",
+                username, CSV_VERSION
+            ),
+        ))
+        .body(contents))
+}
+
+async fn serve_feed(username: &str, name: String, format: Format) -> Result<HttpResponse, Error> {
+    let feeds = CACHE.feeds(username).await.map_err(log_and_crash)?;
+    match feeds.get(&name) {
+        Some(settings) => serve_calendar(username, settings, &Scope::Feed(name), format).await,
+        None => Err(ErrorNotFound("Not found")),
+    }
+}
+
+async fn _calendar(
+    username: &str,
+    params: web::Query<Params>,
+    format: Format,
+) -> Result<impl Responder, Error> {
+    let settings = Settings::try_from(params.into_inner()).map_err(ErrorBadRequest)?;
+    require_public(username).await?;
+    let scope = scope_for(username, &settings).await?;
+    serve_calendar(username, &settings, &scope, format).await
+}
+
 #[get("/{username}.ical")]
-async fn calendar(username: web::Path<String>) -> Result<impl Responder, Error> {
-    _calendar(username).await
+async fn calendar(
+    req: HttpRequest,
+    username: web::Path<String>,
+    params: web::Query<Params>,
+) -> Result<impl Responder, Error> {
+    _calendar(&username, params, format_for(&req, "ical")).await
+}
+
+#[get("/{username}.{extension:ics|jcal|xcal}")]
+async fn calendar_alt(
+    req: HttpRequest,
+    path: web::Path<(String, String)>,
+    params: web::Query<Params>,
+) -> Result<impl Responder, Error> {
+    let (username, extension) = path.into_inner();
+    _calendar(&username, params, format_for(&req, &extension)).await
+}
+
+#[get("/{username}.json")]
+async fn json(
+    username: web::Path<String>,
+    params: web::Query<Params>,
+    pagination: web::Query<Pagination>,
+) -> Result<impl Responder, Error> {
+    let settings = Settings::try_from(params.into_inner()).map_err(ErrorBadRequest)?;
+    require_public(username.as_ref()).await?;
+    serve_json(username.as_ref(), &settings, &pagination).await
+}
+
+#[get("/{username}/private/{secret}.json")]
+async fn private_json(
+    path: web::Path<(String, String)>,
+    params: web::Query<Params>,
+    pagination: web::Query<Pagination>,
+) -> Result<impl Responder, Error> {
+    let (username, secret) = path.into_inner();
+    let settings = Settings::try_from(params.into_inner()).map_err(ErrorBadRequest)?;
+    require_secret(&username, &secret).await?;
+    serve_json(&username, &settings, &pagination).await
+}
+
+#[get("/{username}.atom")]
+async fn atom(
+    username: web::Path<String>,
+    params: web::Query<Params>,
+    trailing: web::Query<Trailing>,
+) -> Result<impl Responder, Error> {
+    let settings = Settings::try_from(params.into_inner()).map_err(ErrorBadRequest)?;
+    require_public(username.as_ref()).await?;
+    let url = format!("{}/{}.atom", base_url(), username);
+    serve_atom(username.as_ref(), &settings, &trailing, url).await
+}
+
+#[get("/{username}.csv")]
+async fn registry(
+    username: web::Path<String>,
+    params: web::Query<Params>,
+    version: web::Query<Version>,
+) -> Result<impl Responder, Error> {
+    let settings = Settings::try_from(params.into_inner()).map_err(ErrorBadRequest)?;
+    version.validate().map_err(ErrorBadRequest)?;
+    require_public(username.as_ref()).await?;
+    serve_registry(username.as_ref(), &settings).await
+}
+
+#[get("/{username}/private/{secret}.csv")]
+async fn private_registry(
+    path: web::Path<(String, String)>,
+    params: web::Query<Params>,
+    version: web::Query<Version>,
+) -> Result<impl Responder, Error> {
+    let (username, secret) = path.into_inner();
+    let settings = Settings::try_from(params.into_inner()).map_err(ErrorBadRequest)?;
+    version.validate().map_err(ErrorBadRequest)?;
+    require_secret(&username, &secret).await?;
+    serve_registry(&username, &settings).await
+}
+
+#[get("/{username}/private/{secret}.atom")]
+async fn private_atom(
+    path: web::Path<(String, String)>,
+    params: web::Query<Params>,
+    trailing: web::Query<Trailing>,
+) -> Result<impl Responder, Error> {
+    let (username, secret) = path.into_inner();
+    let settings = Settings::try_from(params.into_inner()).map_err(ErrorBadRequest)?;
+    require_secret(&username, &secret).await?;
+    let url = format!("{}/{}/private/{}.atom", base_url(), username, secret);
+    serve_atom(&username, &settings, &trailing, url).await
+}
+
+#[get("/{username}/private/{secret}.{extension:ics|jcal|xcal}")]
+async fn private_calendar(
+    req: HttpRequest,
+    path: web::Path<(String, String, String)>,
+    params: web::Query<Params>,
+) -> Result<impl Responder, Error> {
+    let (username, secret, extension) = path.into_inner();
+    let settings = Settings::try_from(params.into_inner()).map_err(ErrorBadRequest)?;
+    require_secret(&username, &secret).await?;
+    let format = format_for(&req, &extension);
+    let scope = scope_for(&username, &settings).await?;
+    serve_calendar(&username, &settings, &scope, format).await
+}
+
+#[get("/{username}/feeds/{name}.{extension:ics|jcal|xcal}")]
+async fn feed_calendar(
+    req: HttpRequest,
+    path: web::Path<(String, String, String)>,
+) -> Result<impl Responder, Error> {
+    let (username, name, extension) = path.into_inner();
+    require_public(&username).await?;
+    serve_feed(&username, name, format_for(&req, &extension)).await
+}
+
+#[get("/{username}/private/{secret}/feeds/{name}.{extension:ics|jcal|xcal}")]
+async fn private_feed_calendar(
+    req: HttpRequest,
+    path: web::Path<(String, String, String, String)>,
+) -> Result<impl Responder, Error> {
+    let (username, secret, name, extension) = path.into_inner();
+    require_secret(&username, &secret).await?;
+    serve_feed(&username, name, format_for(&req, &extension)).await
+}
+
+#[post("/{username}/secret")]
+async fn regenerate_secret(
+    req: HttpRequest,
+    username: web::Path<String>,
+) -> Result<impl Responder, Error> {
+    require_owner(&req, &username).await?;
+    let secret = random_token();
+    CACHE
+        .save_secret(&username, hash(&secret).as_str())
+        .await
+        .map_err(log_and_crash)?;
+    user_page(&req, &username, Some(secret), &TableParams::default()).await
 }
 
-#[get("/{username}.ics")]
-async fn calendar_alt(username: web::Path<String>) -> Result<impl Responder, Error> {
-    _calendar(username).await
+#[post("/{username}/preferences")]
+async fn save_preferences(
+    req: HttpRequest,
+    username: web::Path<String>,
+    form: web::Form<PreferencesForm>,
+) -> Result<impl Responder, Error> {
+    require_owner(&req, &username).await?;
+    let form = form.into_inner();
+    let lang = form.lang.filter(|lang| !lang.is_empty());
+    let preferences = Preferences {
+        public: form.public.is_some(),
+        lang: parse_lang(lang.as_ref()).map_err(ErrorBadRequest)?,
+        leap_day: parse_leap_day(form.leap_day.as_ref()).map_err(ErrorBadRequest)?,
+    };
+    CACHE
+        .save_preferences(&username, &preferences)
+        .await
+        .map_err(log_and_crash)?;
+
+    // calendars relying on the preferred language need to be rendered again
+    let feeds = CACHE.feeds(&username).await.map_err(log_and_crash)?;
+    let mut scopes = vec![Scope::Default];
+    scopes.extend(feeds.into_keys().map(Scope::Feed));
+    for scope in scopes {
+        CACHE
+            .delete_calendar(&username, &scope)
+            .await
+            .map_err(log_and_crash)?;
+    }
+    Ok(Redirect::to(format!("/{}", username)).see_other())
+}
+
+#[post("/{username}/feeds")]
+async fn save_feed(
+    req: HttpRequest,
+    username: web::Path<String>,
+    form: web::Form<FeedForm>,
+) -> Result<impl Responder, Error> {
+    require_owner(&req, &username).await?;
+    let form = form.into_inner();
+    validate_name(&form.name).map_err(ErrorBadRequest)?;
+    let settings = Settings::try_from(form.params).map_err(ErrorBadRequest)?;
+    let settings = validated(settings).await?;
+    let mut feeds = CACHE.feeds(&username).await.map_err(log_and_crash)?;
+    feeds.insert(form.name.clone(), settings);
+    CACHE
+        .save_feeds(&username, &feeds)
+        .await
+        .map_err(log_and_crash)?;
+    CACHE
+        .delete_calendar(&username, &Scope::Feed(form.name))
+        .await
+        .map_err(log_and_crash)?;
+    Ok(Redirect::to(format!("/{}", username)).see_other())
+}
+
+#[post("/{username}/feeds/{name}/delete")]
+async fn delete_feed(
+    req: HttpRequest,
+    path: web::Path<(String, String)>,
+) -> Result<impl Responder, Error> {
+    let (username, name) = path.into_inner();
+    require_owner(&req, &username).await?;
+    let mut feeds = CACHE.feeds(&username).await.map_err(log_and_crash)?;
+    if feeds.remove(&name).is_none() {
+        return Err(ErrorNotFound("Not found"));
+    }
+    CACHE
+        .save_feeds(&username, &feeds)
+        .await
+        .map_err(log_and_crash)?;
+    CACHE
+        .delete_calendar(&username, &Scope::Feed(name))
+        .await
+        .map_err(log_and_crash)?;
+    Ok(Redirect::to(format!("/{}", username)).see_other())
+}
+
+/// Renders the user's page, including the private URL only right after it is (re)generated, since
+/// only its hash is stored.
+async fn user_page(
+    req: &HttpRequest,
+    username: &String,
+    secret: Option<String>,
+    params: &TableParams,
+) -> Result<HttpResponse, Error> {
+    let lang = lang_for(req);
+    let mut ctx = context(Some(username), lang).map_err(log_and_crash)?;
+    let preferences = CACHE.preferences(username).await.map_err(log_and_crash)?;
+    let owner = logged_in_as(req).await.as_ref() == Some(username);
+    ctx.insert(
+        "public".into(),
+        liquid::model::Value::scalar(preferences.public),
+    );
+    if owner || preferences.public {
+        match CACHE.commits(username).await {
+            Ok(commits) => {
+                let table =
+                    table(&commits, params, preferences.leap_day, lang).map_err(log_and_crash)?;
+                ctx.insert("table".into(), liquid::model::Value::Object(table));
+                let failures = CACHE.failures(username).await.map_err(log_and_crash)?;
+                ctx.insert(
+                    "failures".into(),
+                    liquid::model::to_value(&failures).map_err(log_and_crash)?,
+                );
+            }
+            Err(_) => {
+                let job = job(username);
+                let status = if job.as_ref().is_some_and(|job| job.status == Status::Failed) {
+                    "failed"
+                } else {
+                    "loading"
+                };
+                ctx.insert(status.into(), liquid::model::Value::scalar(true));
+                let job = job.unwrap_or_else(|| Job::new(Status::Listing));
+                ctx.insert(
+                    "job".into(),
+                    liquid::model::Value::Object(liquid::object!({
+                        "repos": job.repos,
+                        "resolved": job.resolved,
+                        "failed": job.failed,
+                        "errors": job.errors,
+                        "percent": job.percent(),
+                    })),
+                );
+            }
+        }
+    }
+    ctx.insert(
+        "feed_lang".into(),
+        liquid::model::to_value(&preferences.lang).map_err(log_and_crash)?,
+    );
+    ctx.insert(
+        "leap_day".into(),
+        liquid::model::Value::scalar(leap_day_param(preferences.leap_day)),
+    );
+    if owner {
+        let feeds = CACHE.feeds(username).await.map_err(log_and_crash)?;
+        let names: Vec<&String> = feeds.keys().collect();
+        let has_secret = CACHE
+            .secret(username)
+            .await
+            .map_err(log_and_crash)?
+            .is_some();
+        ctx.insert("owner".into(), liquid::model::Value::scalar(true));
+        ctx.insert(
+            "feeds".into(),
+            liquid::model::to_value(&names).map_err(log_and_crash)?,
+        );
+        ctx.insert(
+            "has_secret".into(),
+            liquid::model::Value::scalar(has_secret),
+        );
+        ctx.insert(
+            "secret".into(),
+            liquid::model::to_value(&secret).map_err(log_and_crash)?,
+        );
+    }
+    TEMPLATES
+        .html
+        .home
+        .render(&ctx)
+        .map(|html| {
+            HttpResponse::build(StatusCode::OK)
+                .content_type(ContentType::html())
+                .body(html)
+        })
+        .map_err(log_and_crash)
+}
+
+/// Generates the default calendar in the background, as a job tracking its progress. Pages reload
+/// while loading, so only one job runs at a time for each user.
+fn generate(username: String, token: String) {
+    let Some(tracker) = Tracker::start(&username) else {
+        return;
+    };
+    tokio::spawn(async move {
+        // a panic would leave the job running forever, blocking the next ones, so it fails instead
+        let (name, running) = (username.clone(), tracker.clone());
+        let result = tokio::spawn(async move {
+            let settings = Settings::default();
+            data_for(
+                token.as_ref(),
+                name.as_ref(),
+                &settings,
+                &Scope::Default,
+                Some(&running),
+            )
+            .await
+        })
+        .await
+        .unwrap_or_else(|e| Err(anyhow::anyhow!("Calendar generation crashed: {}", e)));
+        if let Err(e) = &result {
+            eprintln!("Error creating calendar for {}: {}", username, e);
+            tracker.error(e);
+        }
+        if let Some(job) = tracker.finish(result.is_ok()) {
+            if let Err(e) = CACHE.save_job(&username, &job).await {
+                eprintln!("Error saving job for {}: {}", username, e);
+            }
+        }
+    });
+}
+
+/// The job running in this process, or the last one that finished.
+async fn job_for(username: &str) -> Result<Option<Job>, Error> {
+    match job(username) {
+        Some(job) => Ok(Some(job)),
+        None => CACHE.job(username).await.map_err(log_and_crash),
+    }
+}
+
+#[get("/{username}/job")]
+async fn job_status(
+    req: HttpRequest,
+    username: web::Path<String>,
+) -> Result<impl Responder, Error> {
+    require_visible(&req, &username).await?;
+    match job_for(&username).await? {
+        Some(job) => Ok(HttpResponse::Ok().json(job)),
+        None => Err(ErrorNotFound("Not found")),
+    }
+}
+
+/// Server-Sent Events with the state of the job, every second until it finishes.
+#[get("/{username}/progress")]
+async fn progress(req: HttpRequest, username: web::Path<String>) -> Result<impl Responder, Error> {
+    require_visible(&req, &username).await?;
+    let events = stream::unfold(Some((username.into_inner(), false)), |state| async move {
+        let (username, wait) = state?;
+        if wait {
+            tokio::time::sleep(PROGRESS_INTERVAL).await;
+        }
+        let job = job(&username);
+        let running = job.as_ref().is_some_and(Job::is_running);
+        let event = format!(
+            "event: {}\ndata: {}\n\n",
+            if running { "progress" } else { "done" },
+            serde_json::to_string(&job).unwrap_or_default()
+        );
+        Some((
+            Ok::<_, Error>(web::Bytes::from(event)),
+            running.then_some((username, true)),
+        ))
+    });
+    Ok(HttpResponse::Ok()
+        .content_type("text/event-stream")
+        .insert_header((header::CACHE_CONTROL, "no-cache"))
+        .streaming(events))
+}
+
+/// Pages listing repos are available to the owner, and to everyone if the calendar is public.
+async fn require_visible(req: &HttpRequest, username: &String) -> Result<(), Error> {
+    let preferences = CACHE.preferences(username).await.map_err(log_and_crash)?;
+    if preferences.public || logged_in_as(req).await.as_ref() == Some(username) {
+        Ok(())
+    } else {
+        Err(ErrorNotFound("Not found"))
+    }
+}
+
+async fn calendar_page(
+    req: &HttpRequest,
+    username: &String,
+    year: i32,
+    month: Option<u32>,
+) -> Result<HttpResponse, Error> {
+    validate_year(year).map_err(ErrorBadRequest)?;
+    if let Some(month) = month {
+        validate_month(month).map_err(ErrorBadRequest)?;
+    }
+    require_visible(req, username).await?;
+    let token = CACHE
+        .token(username)
+        .await
+        .map_err(|_| ErrorNotFound("Not found"))?;
+    let lang = lang_for(req);
+    let mut ctx = context(Some(username), lang).map_err(log_and_crash)?;
+    let commits = match CACHE.commits(username).await {
+        Ok(commits) => commits,
+        Err(_) => {
+            generate(username.clone(), token);
+            ctx.insert("loading".into(), liquid::model::Value::scalar(true));
+            vec![]
+        }
+    };
+    let grid = grid(&commits, year, month, lang).map_err(log_and_crash)?;
+    ctx.insert("grid".into(), liquid::model::Value::Object(grid));
+    TEMPLATES
+        .html
+        .calendar
+        .render(&ctx)
+        .map(|html| {
+            HttpResponse::build(StatusCode::OK)
+                .content_type(ContentType::html())
+                .body(html)
+        })
+        .map_err(log_and_crash)
+}
+
+#[get("/{username}/calendar")]
+async fn this_year_calendar(
+    req: HttpRequest,
+    username: web::Path<String>,
+) -> Result<impl Responder, Error> {
+    calendar_page(&req, &username, Local::now().year(), None).await
+}
+
+#[get("/{username}/calendar/{year}")]
+async fn year_calendar(
+    req: HttpRequest,
+    path: web::Path<(String, i32)>,
+) -> Result<impl Responder, Error> {
+    let (username, year) = path.into_inner();
+    calendar_page(&req, &username, year, None).await
+}
+
+#[get("/{username}/calendar/{year}/{month}")]
+async fn month_calendar(
+    req: HttpRequest,
+    path: web::Path<(String, i32, u32)>,
+) -> Result<impl Responder, Error> {
+    let (username, year, month) = path.into_inner();
+    calendar_page(&req, &username, year, Some(month)).await
+}
+
+/// A single repo, from the cache, from its owner's cached repos, or from GitHub using the owner's
+/// token or the server's `GITHUB_TOKEN`.
+async fn repo_commit(owner: &str, name: &str) -> Result<FirstCommit, Error> {
+    validate_repo_name(owner, name).map_err(ErrorBadRequest)?;
+    require_public(owner).await?;
+    if let Some(commit) = CACHE
+        .repo_commit(owner, name)
+        .await
+        .map_err(log_and_crash)?
+    {
+        return Ok(commit);
+    }
+    let cached = CACHE.commits(owner).await.ok().and_then(|commits| {
+        commits
+            .into_iter()
+            .find(|commit| commit.name.eq_ignore_ascii_case(name))
+    });
+    let commit = match cached {
+        // generations leave releases out, so they are fetched with the token of the owner
+        Some(commit) => match CACHE.token(owner).await {
+            Ok(token) => with_releases(&GitHubGraphQL::new(token.as_ref()), commit).await,
+            Err(_) => commit,
+        },
+        None => {
+            let token = match CACHE.token(owner).await {
+                Ok(token) => token,
+                Err(_) => envvar::get("GITHUB_TOKEN").map_err(|_| ErrorNotFound("Not found"))?,
+            };
+            let client = GitHubGraphQL::new(token.as_ref());
+            let repo = repo_for(&client, owner, name)
+                .await
+                .map_err(log_and_crash)?
+                .filter(|repo| !repo.is_private)
+                .ok_or_else(|| ErrorNotFound("Not found"))?;
+            // anyone can ask for any public repo, so only the first commit and its releases are
+            // fetched, and star milestones and firsts come from the cache when calendars found them
+            let commit = last_commit(&client, &repo)
+                .await
+                .map_err(log_and_crash)?
+                .ok_or_else(|| ErrorNotFound("Not found"))?;
+            let mut commit = with_releases(&client, commit).await;
+            let owner = repo.owner.login.as_str();
+            commit.star_milestones = CACHE
+                .star_history(owner, &repo.name)
+                .await
+                .map(|history| history.milestones)
+                .unwrap_or_default();
+            commit.firsts = CACHE
+                .firsts(owner, &repo.name)
+                .await
+                .ok()
+                .flatten()
+                .unwrap_or_default();
+            commit
+        }
+    };
+    CACHE
+        .save_repo_commit(&commit)
+        .await
+        .map_err(log_and_crash)?;
+    Ok(commit)
+}
+
+#[get("/{owner}/{name}.{extension:ics|jcal|xcal}")]
+async fn repo_calendar(
+    req: HttpRequest,
+    path: web::Path<(String, String, String)>,
+    params: web::Query<Params>,
+) -> Result<impl Responder, Error> {
+    let (owner, name, extension) = path.into_inner();
+    let settings = Settings::try_from(params.into_inner()).map_err(ErrorBadRequest)?;
+    let commit = repo_commit(&owner, &name).await?;
+    let settings = validated(settings).await?;
+    let settings = with_preferred_lang(&owner, &settings)
+        .await
+        .map_err(log_and_crash)?;
+    let title = format!("{}/{}", commit.owner, commit.name);
+    let contents = render_calendar(&title, vec![commit], vec![], &settings)
+        .await
+        .map_err(log_and_crash)?;
+    calendar_response(contents, format_for(&req, &extension))
+}
+
+#[derive(Deserialize)]
+struct BadgeParams {
+    lang: Option<String>,
+}
+
+#[get("/{owner}/{name}/badge.svg")]
+async fn repo_badge(
+    path: web::Path<(String, String)>,
+    params: web::Query<BadgeParams>,
+) -> Result<impl Responder, Error> {
+    let (owner, name) = path.into_inner();
+    let lang = parse_lang(params.lang.as_ref())
+        .map_err(ErrorBadRequest)?
+        .unwrap_or(DEFAULT_LANGUAGE.to_string());
+    let commit = repo_commit(&owner, &name).await?;
+    let mut args = FluentArgs::new();
+    args.set("age", age(&commit).map_err(log_and_crash)?);
+    let body = svg(
+        &I18N.message(&lang, "badge-label", None),
+        &I18N.message(&lang, "badge-age", Some(&args)),
+    );
+    Ok(HttpResponse::Ok()
+        .content_type("image/svg+xml")
+        .insert_header((header::CACHE_CONTROL, "max-age=3600"))
+        .body(body))
+}
+
+#[get("/{username}/timeline/{name}")]
+async fn timeline_page(
+    req: HttpRequest,
+    path: web::Path<(String, String)>,
+) -> Result<impl Responder, Error> {
+    let (username, name) = path.into_inner();
+    require_visible(&req, &username).await?;
+    let commits = CACHE
+        .commits(&username)
+        .await
+        .map_err(|_| ErrorNotFound("Not found"))?;
+    let mut commit = commits
+        .into_iter()
+        .find(|commit| commit.name == name)
+        .ok_or_else(|| ErrorNotFound("Not found"))?;
+    if let Ok(token) = CACHE.token(&username).await {
+        let client = GitHubGraphQL::new(token.as_ref());
+        commit = with_releases(&client, commit).await;
+        commit.star_milestones = star_milestones_for(&client, &commit).await;
+        commit.firsts = firsts_for(&client, &commit).await;
+    }
+    let lang = lang_for(&req);
+    let mut ctx = context(Some(&username), lang).map_err(log_and_crash)?;
+    ctx.insert(
+        "repo".into(),
+        liquid::model::Value::Object(liquid::object!({
+            "name": commit.name,
+            "url": commit.url,
+            "description": commit.description,
+        })),
+    );
+    ctx.insert(
+        "timeline".into(),
+        liquid::model::to_value(&timeline(&commit, lang)).map_err(log_and_crash)?,
+    );
+    TEMPLATES
+        .html
+        .timeline
+        .render(&ctx)
+        .map(|html| {
+            HttpResponse::build(StatusCode::OK)
+                .content_type(ContentType::html())
+                .body(html)
+        })
+        .map_err(log_and_crash)
+}
+
+/// Decisions about every repo GitHub returns, for the default calendar, a saved feed, or the
+/// options in the query string.
+async fn explanation(
+    req: &HttpRequest,
+    username: &str,
+    params: web::Query<Params>,
+    explain_params: &ExplainParams,
+) -> Result<Vec<Entry>, Error> {
+    require_owner(req, username).await?;
+    let settings = match &explain_params.feed {
+        Some(name) => CACHE
+            .feeds(username)
+            .await
+            .map_err(log_and_crash)?
+            .remove(name)
+            .ok_or_else(|| ErrorNotFound("Not found"))?,
+        None => Settings::try_from(params.into_inner()).map_err(ErrorBadRequest)?,
+    };
+    let token = CACHE
+        .token(username)
+        .await
+        .map_err(|_| ErrorNotFound("Not found"))?;
+    let client = GitHubGraphQL::new(token.as_ref());
+    let repos = all_repos_for(&client, username)
+        .await
+        .map_err(log_and_crash)?;
+    let commits = match CACHE.commits(username).await {
+        Ok(commits) => Some(with_extras(&token, commits, &settings).await),
+        Err(_) => {
+            generate(username.to_string(), token);
+            None
+        }
+    };
+    let failures = CACHE.failures(username).await.map_err(log_and_crash)?;
+    explain(&repos, commits.as_deref(), &failures, &settings).map_err(log_and_crash)
+}
+
+#[get("/{username}/explain.json")]
+async fn explain_json(
+    req: HttpRequest,
+    username: web::Path<String>,
+    params: web::Query<Params>,
+    explain_params: web::Query<ExplainParams>,
+) -> Result<impl Responder, Error> {
+    let entries = explanation(&req, &username, params, &explain_params).await?;
+    Ok(HttpResponse::Ok().json(entries))
+}
+
+#[get("/{username}/explain")]
+async fn explain_page(
+    req: HttpRequest,
+    username: web::Path<String>,
+    params: web::Query<Params>,
+    explain_params: web::Query<ExplainParams>,
+) -> Result<impl Responder, Error> {
+    let entries = explanation(&req, &username, params, &explain_params).await?;
+    let lang = lang_for(&req);
+    let mut ctx = context(Some(&username), lang).map_err(log_and_crash)?;
+    let query = match req.query_string() {
+        "" => String::new(),
+        query => format!("?{}", query),
+    };
+    ctx.insert("query".into(), liquid::model::Value::scalar(query));
+    ctx.insert(
+        "entries".into(),
+        liquid::model::to_value(&rows(&entries, lang).map_err(log_and_crash)?)
+            .map_err(log_and_crash)?,
+    );
+    TEMPLATES
+        .html
+        .explain
+        .render(&ctx)
+        .map(|html| {
+            HttpResponse::build(StatusCode::OK)
+                .content_type(ContentType::html())
+                .body(html)
+        })
+        .map_err(log_and_crash)
 }
 
 #[get("/{username}")]
-async fn view(username: web::Path<String>) -> Result<impl Responder, Error> {
+async fn view(
+    req: HttpRequest,
+    username: web::Path<String>,
+    params: web::Query<TableParams>,
+) -> Result<impl Responder, Error> {
     if let Ok(token) = CACHE.token(username.as_ref()).await {
-        if CACHE.calendar(username.as_ref()).await.is_err() {
-            let user = username.clone();
-            tokio::spawn(async move {
-                if let Err(e) = data_for(token.as_ref(), user.as_ref()).await {
-                    eprintln!("Error creating calendar for {}: {}", user, e);
-                }
-            });
-        }
-        TEMPLATES
-            .html
-            .home
-            .render(&context(Some(&username)).map_err(log_and_crash)?)
-            .map(|html| {
-                HttpResponse::build(StatusCode::OK)
-                    .content_type(ContentType::html())
-                    .body(html)
-            })
-            .map_err(log_and_crash)
+        let missing = CACHE.commits(username.as_ref()).await.is_err()
+            || CACHE
+                .calendar(username.as_ref(), &Scope::Default)
+                .await
+                .is_err();
+        if missing {
+            generate(username.to_string(), token);
+        }
+        user_page(&req, &username, None, &params).await
     } else {
         Err(ErrorNotFound("Not found"))
     }
 }
+
+/// First commits of a registered organization, from the cache or from GitHub.
+async fn org_commits_for(
+    org: &str,
+    private: bool,
+) -> Result<(Vec<FirstCommit>, OrgRegistration), Error> {
+    let registration = CACHE
+        .org(org)
+        .await
+        .map_err(log_and_crash)?
+        .ok_or_else(|| ErrorNotFound("Not found"))?;
+    if let Ok(commits) = CACHE.org_commits(org, private).await {
+        return Ok((commits, registration));
+    }
+    let commits = fetch_org_commits(org, &registration, private)
+        .await
+        .map_err(log_and_crash)?;
+    Ok((commits, registration))
+}
+
+async fn serve_org_calendar(
+    org: &str,
+    private: bool,
+    settings: &Settings,
+    format: Format,
+) -> Result<HttpResponse, Error> {
+    let (commits, registration) = org_commits_for(org, private).await?;
+    let settings = &validated(settings.clone()).await?;
+    let token = CACHE
+        .token(&registration.member)
+        .await
+        .map_err(log_and_crash)?;
+    let mut commits = with_extras(&token, commits, settings).await;
+    commits.sort_by_cached_key(|commit| {
+        commit
+            .days_to_next_anniversary(settings.leap_day)
+            .unwrap_or(i64::MAX)
+    });
+    let contents = render_calendar(org, commits, vec![], settings)
+        .await
+        .map_err(log_and_crash)?;
+    calendar_response(contents, format)
+}
+
+#[get("/orgs/{org}.{extension:ics|jcal|xcal}")]
+async fn org_calendar(
+    req: HttpRequest,
+    path: web::Path<(String, String)>,
+    params: web::Query<Params>,
+) -> Result<impl Responder, Error> {
+    let (org, extension) = path.into_inner();
+    let org = org.to_lowercase();
+    validate_owner(&org).map_err(ErrorBadRequest)?;
+    let settings = Settings::try_from(params.into_inner()).map_err(ErrorBadRequest)?;
+    serve_org_calendar(&org, false, &settings, format_for(&req, &extension)).await
+}
+
+#[get("/orgs/{org}/private/{secret}.{extension:ics|jcal|xcal}")]
+async fn private_org_calendar(
+    req: HttpRequest,
+    path: web::Path<(String, String, String)>,
+    params: web::Query<Params>,
+) -> Result<impl Responder, Error> {
+    let (org, secret, extension) = path.into_inner();
+    let org = org.to_lowercase();
+    validate_owner(&org).map_err(ErrorBadRequest)?;
+    let settings = Settings::try_from(params.into_inner()).map_err(ErrorBadRequest)?;
+    match CACHE.org_secret(&org).await.map_err(log_and_crash)? {
+        Some(expected) if expected == hash(&secret) => {}
+        _ => return Err(ErrorNotFound("Not found")),
+    }
+    serve_org_calendar(&org, true, &settings, format_for(&req, &extension)).await
+}
+
+/// Members who granted access to their organizations register them, getting a new secret. Private
+/// repos are only listed if they also granted access to private repos.
+#[post("/orgs")]
+async fn register_org(req: HttpRequest, form: web::Form<OrgForm>) -> Result<impl Responder, Error> {
+    let username = logged_in_as(&req)
+        .await
+        .ok_or_else(|| ErrorForbidden("Forbidden"))?;
+    let org = form.org.trim().to_lowercase();
+    validate_owner(&org).map_err(ErrorBadRequest)?;
+    let token = CACHE.token(&username).await.map_err(log_and_crash)?;
+    let scopes = scopes_for(&Client::new(), &token)
+        .await
+        .map_err(log_and_crash)?;
+    if !scopes
+        .iter()
+        .any(|scope| ORG_SCOPES.contains(&scope.as_str()))
+    {
+        return Err(ErrorForbidden(
+            "Log in again granting access to your organizations",
+        ));
+    }
+    let client = GitHubGraphQL::new(token.as_ref());
+    let membership = membership(&client, &org)
+        .await
+        .map_err(log_and_crash)?
+        .filter(|membership| membership.viewer_is_a_member)
+        .ok_or_else(|| ErrorForbidden("Forbidden"))?;
+    // Registering again replaces the secret, revoking the private URL, so only the member who
+    // registered the organization or one of its admins can do it.
+    if let Some(existing) = CACHE.org(&org).await.map_err(log_and_crash)? {
+        if existing.member != username && !membership.viewer_can_administer {
+            return Err(ErrorForbidden(
+                "Already registered by another member of the organization",
+            ));
+        }
+    }
+    let registration = OrgRegistration {
+        member: username.clone(),
+        private: scopes.iter().any(|scope| scope == PRIVATE_REPOS_SCOPE),
+    };
+    let secret = random_token();
+    CACHE
+        .save_org(&org, &registration)
+        .await
+        .map_err(log_and_crash)?;
+    CACHE
+        .save_org_secret(&org, hash(&secret).as_str())
+        .await
+        .map_err(log_and_crash)?;
+    let name = org.clone();
+    let member = registration.clone();
+    tokio::spawn(async move {
+        if let Err(e) = fetch_org_commits(&name, &member, false).await {
+            eprintln!("Error creating calendar for {}: {}", name, e);
+        }
+    });
+
+    let lang = lang_for(&req);
+    let mut ctx = context(Some(&username), lang).map_err(log_and_crash)?;
+    ctx.insert("org".into(), liquid::model::Value::scalar(org));
+    ctx.insert("secret".into(), liquid::model::Value::scalar(secret));
+    ctx.insert(
+        "private".into(),
+        liquid::model::Value::scalar(registration.private),
+    );
+    TEMPLATES
+        .html
+        .org
+        .render(&ctx)
+        .map(|html| {
+            HttpResponse::build(StatusCode::OK)
+                .content_type(ContentType::html())
+                .body(html)
+        })
+        .map_err(log_and_crash)
+}